@@ -0,0 +1,60 @@
+/// Uppercases the first letter of `word`, leaving the rest as it is.
+pub(crate) fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Lowercases the first letter of `word`, leaving the rest as it is.
+pub(crate) fn lowercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Whether `word` starts with a capital letter followed only by lowercase letters, as in "Paris".
+pub(crate) fn is_capitalized(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(char::is_uppercase) && !chars.any(char::is_uppercase)
+}
+
+/// Capitalizes `word` if `original`, the word it replaces, starts with a capital letter.
+pub(crate) fn match_case(original: &str, word: &str) -> String {
+    if original.starts_with(char::is_uppercase) {
+        capitalize_first(word)
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capitalize_and_lowercase_first() {
+        assert_eq!(capitalize_first("you're"), "You're");
+        assert_eq!(capitalize_first("éTÉ"), "ÉTÉ");
+        assert_eq!(lowercase_first("NASA"), "nASA");
+        assert_eq!(lowercase_first(""), "");
+    }
+
+    #[test]
+    fn test_is_capitalized() {
+        assert!(is_capitalized("Paris"));
+        assert!(is_capitalized("I"));
+        assert!(!is_capitalized("NASA"));
+        assert!(!is_capitalized("paris"));
+        assert!(!is_capitalized(""));
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("Their", "there"), "There");
+        assert_eq!(match_case("their", "there"), "there");
+    }
+}
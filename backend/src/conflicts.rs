@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use crate::models::SpacingOptions;
+use crate::span::Span;
+
+/// The minimum distance to keep between mutations, measured in the passage being mutated.
+#[derive(Debug)]
+struct Spacing<'a> {
+    text: &'a str,
+    options: SpacingOptions,
+
+    /// The spans of the passage's words, in order.
+    words: Vec<Span>,
+}
+
+impl Spacing<'_> {
+    /// Whether the text between `from` and `to` is shorter than the minimum distance.
+    fn too_close(&self, from: usize, to: usize) -> bool {
+        let gap = &self.text[from..to];
+
+        let too_few_chars =
+            self.options.min_chars > 0 && gap.chars().nth(self.options.min_chars - 1).is_none();
+
+        too_few_chars || self.words_between(from, to) < self.options.min_words
+    }
+
+    /// The number of words lying wholly between `from` and `to`.
+    fn words_between(&self, from: usize, to: usize) -> usize {
+        let started_before = self.words.partition_point(|w| w.start < from);
+        let ended_by = self.words.partition_point(|w| w.end <= to);
+
+        ended_by.saturating_sub(started_before)
+    }
+}
+
+/// Tracks the parts of a passage already claimed by selected mutations, so that every mutation
+/// touches its own region of text.
+///
+/// Spans which merely touch are also treated as conflicting: two edits side by side tend to read
+/// as a single error, which would make the reported count disagree with what a reader can find.
+///
+/// A minimum spacing can also be required, so that errors don't cluster in one word or line.
+#[derive(Debug, Default)]
+pub(crate) struct ConflictResolver<'a> {
+    /// Claimed spans, keyed by their start. Reserved spans never touch one another, so they are
+    /// ordered by their ends as well.
+    reserved: BTreeMap<usize, Span>,
+
+    spacing: Option<Spacing<'a>>,
+}
+
+impl<'a> ConflictResolver<'a> {
+    /// Creates a resolver which also keeps mutations of `text` at least as far apart as
+    /// `options` requires.
+    pub(crate) fn with_spacing(text: &'a str, options: SpacingOptions) -> Self {
+        if options == SpacingOptions::default() {
+            return ConflictResolver::default();
+        }
+
+        let mut words = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            match (start, c.is_alphanumeric() || c == '\'') {
+                (None, true) => start = Some(i),
+                (Some(s), false) => {
+                    words.push(Span::new(s, i));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        ConflictResolver {
+            reserved: BTreeMap::new(),
+            spacing: Some(Spacing {
+                text,
+                options,
+                words,
+            }),
+        }
+    }
+
+    /// Whether `span` overlaps or touches a span which has already been reserved, or is closer
+    /// to one than the minimum spacing allows.
+    pub(crate) fn conflicts(&self, span: Span) -> bool {
+        // Of the reservations starting no later than `span` ends, the last reaches furthest.
+        let overlaps = self
+            .reserved
+            .range(..=span.end)
+            .next_back()
+            .is_some_and(|(_, r)| r.touches(span));
+
+        if overlaps {
+            return true;
+        }
+
+        let Some(spacing) = &self.spacing else {
+            return false;
+        };
+
+        // Only the nearest reservation on either side can be too close.
+        let before = self.reserved.range(..span.start).next_back();
+        let after = self.reserved.range(span.start..).next();
+
+        before.is_some_and(|(_, r)| spacing.too_close(r.end, span.start))
+            || after.is_some_and(|(_, r)| spacing.too_close(span.end, r.start))
+    }
+
+    /// Reserves `span` if it does not conflict with an existing reservation.
+    /// Returns whether the reservation was made.
+    pub(crate) fn try_reserve(&mut self, span: Span) -> bool {
+        if self.conflicts(span) {
+            return false;
+        }
+
+        self.reserved.insert(span.start, span);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_ranges_are_reserved() {
+        let mut resolver = ConflictResolver::default();
+        assert!(resolver.try_reserve(Span::new(5, 7)));
+        assert!(resolver.try_reserve(Span::new(0, 2)));
+        assert!(resolver.try_reserve(Span::new(10, 12)));
+        assert_eq!(
+            resolver.reserved.values().copied().collect::<Vec<_>>(),
+            vec![Span::new(0, 2), Span::new(5, 7), Span::new(10, 12)]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_ranges_conflict() {
+        let mut resolver = ConflictResolver::default();
+        assert!(resolver.try_reserve(Span::new(2, 6)));
+        assert!(!resolver.try_reserve(Span::new(0, 3)));
+        assert!(!resolver.try_reserve(Span::new(5, 8)));
+        assert!(!resolver.try_reserve(Span::new(3, 4)));
+        assert!(!resolver.try_reserve(Span::new(0, 10)));
+    }
+
+    #[test]
+    fn test_touching_ranges_conflict() {
+        let mut resolver = ConflictResolver::default();
+        assert!(resolver.try_reserve(Span::new(2, 4)));
+        assert!(!resolver.try_reserve(Span::new(0, 2)));
+        assert!(!resolver.try_reserve(Span::new(4, 5)));
+        assert!(resolver.try_reserve(Span::new(5, 6)));
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let mut resolver = ConflictResolver::default();
+        assert!(resolver.try_reserve(Span::new(3, 3)));
+        assert!(!resolver.try_reserve(Span::new(3, 3)));
+        assert!(!resolver.try_reserve(Span::new(1, 3)));
+        assert!(resolver.try_reserve(Span::new(5, 5)));
+    }
+
+    #[test]
+    fn test_minimum_character_spacing() {
+        let options = SpacingOptions {
+            min_chars: 3,
+            min_words: 0,
+        };
+        let mut resolver = ConflictResolver::with_spacing("abcdéfghij", options);
+        assert!(resolver.try_reserve(Span::new(0, 1)));
+        assert!(!resolver.try_reserve(Span::new(3, 4)));
+        // "bcd" lies between.
+        assert!(resolver.try_reserve(Span::new(4, 6)));
+        assert!(!resolver.try_reserve(Span::new(8, 9)));
+        assert!(resolver.try_reserve(Span::new(9, 10)));
+    }
+
+    #[test]
+    fn test_minimum_word_spacing() {
+        let text = "one two three four five six";
+        let options = SpacingOptions {
+            min_chars: 0,
+            min_words: 2,
+        };
+        let mut resolver = ConflictResolver::with_spacing(text, options);
+        assert!(resolver.try_reserve(Span::new(9, 10)));
+        // Only "two" lies wholly between the mutations.
+        assert!(!resolver.try_reserve(Span::new(1, 2)));
+        assert!(!resolver.try_reserve(Span::new(19, 20)));
+        // "four" and "five" lie between.
+        assert!(resolver.try_reserve(Span::new(24, 25)));
+    }
+}
@@ -0,0 +1,64 @@
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::word_sets;
+
+/// Words which are commonly confused without sounding the same, such as "then" and "than"
+#[derive(Debug, Deserialize)]
+pub(crate) struct ConfusableSets {
+    pub(crate) sets: Vec<Vec<String>>,
+}
+
+impl ConfusableSets {
+    #[cfg(test)]
+    pub(crate) fn new_for_tests() -> Self {
+        ConfusableSets {
+            sets: vec![
+                vec!["then".to_string(), "than".to_string()],
+                vec!["affect".to_string(), "effect".to_string()],
+                vec!["lose".to_string(), "loose".to_string()],
+                vec!["lead".to_string(), "led".to_string()],
+                vec!["principal".to_string(), "principle".to_string()],
+                vec![
+                    "through".to_string(),
+                    "though".to_string(),
+                    "thorough".to_string(),
+                ],
+            ],
+        }
+    }
+
+    pub(crate) fn find_matching_set(&self, word: &str) -> Option<&Vec<String>> {
+        word_sets::find_matching_set(&self.sets, word)
+    }
+
+    pub(crate) fn get_alternative<R: Rng>(&self, word: &str, rng: &mut R) -> Option<String> {
+        word_sets::get_alternative(&self.sets, word, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matching_set() {
+        let cs = ConfusableSets::new_for_tests();
+        assert!(
+            cs.find_matching_set("Led")
+                .unwrap()
+                .contains(&"lead".to_string())
+        );
+        assert!(cs.find_matching_set("your").is_none());
+    }
+
+    #[test]
+    fn test_get_alternative() {
+        let cs = ConfusableSets::new_for_tests();
+        let mut rng = rand::rng();
+        assert_eq!(cs.get_alternative("Then", &mut rng).unwrap(), "Than");
+
+        let alt = cs.get_alternative("through", &mut rng).unwrap();
+        assert!(alt == "though" || alt == "thorough");
+    }
+}
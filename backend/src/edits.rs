@@ -0,0 +1,83 @@
+use crate::span::Span;
+
+/// A replacement of part of the original passage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Edit {
+    /// The text being replaced, in the original passage.
+    pub(crate) span: Span,
+
+    /// The text to put in its place.
+    pub(crate) replacement: String,
+}
+
+/// Applies `edits` to `text` in a single pass.
+///
+/// The edits must be sorted by position and must not overlap. Returns the edited text together
+/// with the span each replacement occupies in it, in the same order as `edits`.
+pub(crate) fn apply_edits(text: &str, edits: &[Edit]) -> (String, Vec<Span>) {
+    let added: usize = edits.iter().map(|e| e.replacement.len()).sum();
+    let mut result = String::with_capacity(text.len() + added);
+    let mut spans = Vec::with_capacity(edits.len());
+    let mut position = 0;
+
+    for edit in edits {
+        debug_assert!(
+            edit.span.start >= position,
+            "edits must be sorted and must not overlap"
+        );
+
+        result.push_str(&text[position..edit.span.start]);
+
+        let start = result.len();
+        result.push_str(&edit.replacement);
+        spans.push(Span::new(start, result.len()));
+
+        position = edit.span.end;
+    }
+
+    result.push_str(&text[position..]);
+
+    (result, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(start: usize, end: usize, replacement: &str) -> Edit {
+        Edit {
+            span: Span::new(start, end),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_no_edits() {
+        assert_eq!(apply_edits("hello", &[]), ("hello".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_apply_edits_tracks_shifts() {
+        let text = "your text, to test";
+        let edits = [edit(0, 4, "you're"), edit(9, 10, ""), edit(11, 13, "two")];
+
+        let (result, spans) = apply_edits(text, &edits);
+
+        assert_eq!(result, "you're text two test");
+        assert_eq!(
+            spans,
+            vec![Span::new(0, 6), Span::new(11, 11), Span::new(12, 15)]
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_at_boundaries() {
+        let text = "abc";
+        let edits = [edit(0, 0, ">"), edit(3, 3, "<")];
+
+        let (result, spans) = apply_edits(text, &edits);
+
+        assert_eq!(result, ">abc<");
+        assert_eq!(spans, vec![Span::new(0, 1), Span::new(4, 5)]);
+    }
+}
@@ -8,16 +8,21 @@ use std::sync::Arc;
 use tracing::info;
 
 use crate::{
-    homophones::HomophoneSets,
+    kinds::MutationRegistry,
     models::{
-        Mutation, MutationRequest, MutationResponse, MutationResponseItem, MutationResponseType,
+        Mutation, MutationKindOptions, MutationKindResponse, MutationKindShortfallResponse,
+        MutationRequest, MutationRequestOptions, MutationResponse, MutationResponseItem,
+        MutationResponseType, MutationResult, MutationShortfallResponse, OffsetUnit,
+        PresetResponse, RestoreRequest, RestoreResponse,
     },
-    mutator::TextMutator,
+    mutator::{EnabledKind, TextMutator},
+    presets::{PRESETS, find_preset},
+    span::{OffsetConverter, Span},
 };
 
 #[derive(Clone)]
 pub struct AppState {
-    pub homophones: Arc<HomophoneSets>,
+    pub registry: Arc<MutationRegistry>,
 }
 
 pub async fn health() -> &'static str {
@@ -31,13 +36,56 @@ pub async fn fallback(uri: Uri) -> (StatusCode, String) {
     )
 }
 
+pub async fn mutations(State(state): State<AppState>) -> Json<Vec<MutationKindResponse>> {
+    let kinds = state
+        .registry
+        .kinds()
+        .iter()
+        .map(|kind| MutationKindResponse {
+            name: kind.name().to_string(),
+            description: kind.description().to_string(),
+        })
+        .collect();
+
+    Json(kinds)
+}
+
+pub async fn presets() -> Json<Vec<PresetResponse>> {
+    let presets = PRESETS
+        .iter()
+        .map(|preset| PresetResponse {
+            name: preset.name.to_string(),
+            description: preset.description.to_string(),
+            mutation_rate: preset.mutation_rate,
+            strategy: preset.strategy,
+            spacing: preset.spacing,
+            mutations: preset
+                .kinds
+                .iter()
+                .map(|&(name, weight)| {
+                    let options = MutationKindOptions {
+                        enabled: Some(true),
+                        weight: Some(weight),
+                        ..Default::default()
+                    };
+
+                    (name.to_string(), options)
+                })
+                .collect(),
+        })
+        .collect();
+
+    Json(presets)
+}
+
 /// Arbitrary amount, chosen just to prevent degenerate requests.
-pub const MAX_INPUT_LENGTH: usize = 5000;
+/// Mutations are applied in a single pass, so long documents are cheap to process.
+pub const MAX_INPUT_LENGTH: usize = 200_000;
 
 #[axum::debug_handler]
 pub async fn mutate(
     State(state): State<AppState>,
-    Json(payload): Json<MutationRequest>,
+    Json(mut payload): Json<MutationRequest>,
 ) -> impl IntoResponse {
     let length = payload.text.chars().count();
 
@@ -49,47 +97,27 @@ pub async fn mutate(
         return (StatusCode::BAD_REQUEST, error).into_response();
     }
 
-    // Set mutation flags
-    let swap_letters = payload.config.allow_swaps;
-    let remove_punctuation = payload.config.allow_punctuation_removal;
-    let homophones = payload.config.allow_homophones;
+    let kinds = match resolve_kinds(&state.registry, &mut payload.config) {
+        Ok(kinds) => kinds,
+        Err(error) => return (StatusCode::BAD_REQUEST, error).into_response(),
+    };
 
     // Apply mutations
     let mut text_mutator = TextMutator::new(
-        payload.config.mutation_rate,
+        payload.config.mutation_rate(),
+        payload.config.strategy(),
         payload.config.seed,
-        swap_letters,
-        remove_punctuation,
-        homophones,
-        state.homophones,
-    );
-
-    let response = text_mutator.mutate(&payload.text);
-
-    let response = MutationResponse {
-        mutated_text: response.mutated_text,
-        mutations: response
-            .mutations
-            .iter()
-            .map(|f| match f {
-                Mutation::SwapLetters(i) => MutationResponseItem {
-                    start: *i,
-                    end: *i,
-                    r#type: MutationResponseType::SwapLetters,
-                },
-                Mutation::RemovePunctuation(i) => MutationResponseItem {
-                    start: *i,
-                    end: *i,
-                    r#type: MutationResponseType::RemovePunctuation,
-                },
-                Mutation::ReplaceHomophone(i, e) => MutationResponseItem {
-                    start: *i,
-                    end: *e,
-                    r#type: MutationResponseType::ReplaceHomophone,
-                },
-            })
-            .collect(),
-    };
+        kinds,
+    )
+    .with_count(payload.config.count)
+    .with_protection(payload.config.protect)
+    .with_format(payload.config.format)
+    .with_spacing(payload.config.spacing())
+    .with_marks(payload.config.mark_mutations);
+
+    let result = text_mutator.mutate(&payload.text);
+
+    let response = build_response(&payload.text, payload.config.offset_unit, result);
 
     let debug_response = serde_json::to_string(&response);
 
@@ -101,3 +129,162 @@ pub async fn mutate(
 
     Json(response).into_response()
 }
+
+#[axum::debug_handler]
+pub async fn restore(
+    State(state): State<AppState>,
+    Json(payload): Json<RestoreRequest>,
+) -> impl IntoResponse {
+    let length = payload.mutated_text.chars().count();
+
+    if length > MAX_INPUT_LENGTH {
+        let error = format!(
+            "The input text was over the max length of {MAX_INPUT_LENGTH} characters ({length})"
+        );
+
+        return (StatusCode::BAD_REQUEST, error).into_response();
+    }
+
+    let mut offsets = OffsetConverter::new(&payload.mutated_text, payload.offset_unit);
+    let mut mutations = Vec::with_capacity(payload.mutations.len());
+
+    for item in payload.mutations {
+        let Some(kind) = state.registry.get(&item.r#type) else {
+            let error = format!("Unknown mutation type: {}", item.r#type);
+
+            return (StatusCode::BAD_REQUEST, error).into_response();
+        };
+
+        let Some(span) = offsets.byte_span(item.start, item.end) else {
+            let error = format!(
+                "The span {}..{} doesn't fit the mutated text",
+                item.start, item.end
+            );
+
+            return (StatusCode::BAD_REQUEST, error).into_response();
+        };
+
+        mutations.push(Mutation {
+            kind: kind.name(),
+            span,
+            // Only the mutated text's side is needed to undo a mutation.
+            original_span: Span::default(),
+            original: item.original,
+            replacement: item.replacement,
+        });
+    }
+
+    match TextMutator::restore(&payload.mutated_text, &mutations) {
+        Ok(text) => Json(RestoreResponse { text }).into_response(),
+        Err(error) => {
+            let error = error.map_offset(|byte| offsets.convert(byte));
+
+            (StatusCode::BAD_REQUEST, error.to_string()).into_response()
+        }
+    }
+}
+
+/// Applies the request's preset, if any, then validates the per-kind settings and selects the
+/// enabled mutation kinds. Returns an error message describing any invalid setting.
+fn resolve_kinds(
+    registry: &MutationRegistry,
+    config: &mut MutationRequestOptions,
+) -> Result<Vec<EnabledKind>, String> {
+    if let Some(name) = config.preset.take() {
+        let Some(preset) = find_preset(&name) else {
+            return Err(format!("Unknown preset: {name}"));
+        };
+
+        config.apply_preset(preset);
+    }
+
+    if let Some((name, weight)) = config.mutations.iter().find_map(|(name, o)| {
+        o.weight
+            .filter(|w| !w.is_finite() || *w < 0.0)
+            .map(|w| (name, w))
+    }) {
+        return Err(format!(
+            "The weight for {name} must be a non-negative number ({weight})"
+        ));
+    }
+
+    if let Some((name, options)) = config
+        .mutations
+        .iter()
+        .find(|(_, o)| o.min.zip(o.max).is_some_and(|(min, max)| min > max))
+    {
+        return Err(format!(
+            "The min for {name} must not exceed its max ({} > {})",
+            options.min.unwrap_or_default(),
+            options.max.unwrap_or_default()
+        ));
+    }
+
+    // Select the enabled mutation kinds
+    let kinds: Vec<EnabledKind> = registry
+        .enabled(|name| config.is_enabled(name))
+        .into_iter()
+        .map(|kind| EnabledKind {
+            weight: config.weight(kind.name()),
+            min: config.min_count(kind.name()),
+            max: config.max_count(kind.name()),
+            kind: kind.configure(config).unwrap_or(kind),
+        })
+        .collect();
+
+    let minimums: usize = kinds.iter().map(|k| k.min).sum();
+
+    if let Some(count) = config.count.filter(|&count| count < minimums) {
+        return Err(format!(
+            "The count must be at least the sum of the enabled kinds' minimums ({count} < {minimums})"
+        ));
+    }
+
+    Ok(kinds)
+}
+
+/// Builds the response for a mutated passage, measuring offsets in the given unit.
+fn build_response(text: &str, unit: OffsetUnit, result: MutationResult) -> MutationResponse {
+    // Mutations are in order of position, so both converters only ever scan forwards.
+    let mut original_offsets = OffsetConverter::new(text, unit);
+    let mut mutated_offsets = OffsetConverter::new(&result.mutated_text, unit);
+
+    let mutations = result
+        .mutations
+        .into_iter()
+        .map(|f| {
+            let (start, end) = mutated_offsets.convert_span(f.span);
+            let (original_start, original_end) = original_offsets.convert_span(f.original_span);
+
+            MutationResponseItem {
+                start,
+                end,
+                original_start,
+                original_end,
+                original: f.original,
+                replacement: f.replacement,
+                r#type: MutationResponseType(f.kind.to_string()),
+            }
+        })
+        .collect();
+
+    let shortfall = result.shortfall.map(|shortfall| MutationShortfallResponse {
+        requested: shortfall.requested,
+        applied: shortfall.applied,
+        kinds: shortfall
+            .kinds
+            .into_iter()
+            .map(|kind| MutationKindShortfallResponse {
+                r#type: MutationResponseType(kind.kind.to_string()),
+                minimum: kind.minimum,
+                applied: kind.applied,
+            })
+            .collect(),
+    });
+
+    MutationResponse {
+        mutated_text: result.mutated_text,
+        mutations,
+        shortfall,
+    }
+}
@@ -1,6 +1,8 @@
-use rand::{Rng, seq::IndexedRandom};
+use rand::Rng;
 use serde::Deserialize;
 
+use crate::word_sets;
+
 /// Common homophones that can be swapped
 #[derive(Debug, Deserialize)]
 pub(crate) struct HomophoneSets {
@@ -13,49 +15,26 @@ impl HomophoneSets {
         HomophoneSets {
             sets: vec![
                 vec!["your".to_string(), "you're".to_string()],
-                vec!["their".to_string(), "there".to_string(), "they're".to_string()],
+                vec![
+                    "their".to_string(),
+                    "there".to_string(),
+                    "they're".to_string(),
+                ],
                 vec!["its".to_string(), "it's".to_string()],
                 vec!["to".to_string(), "too".to_string(), "two".to_string()],
-                vec!["than".to_string(), "then".to_string()],
-                vec!["affect".to_string(), "effect".to_string()],
-                vec!["accept".to_string(), "except".to_string()],
                 vec!["who's".to_string(), "whose".to_string()],
                 vec!["which".to_string(), "witch".to_string()],
                 vec!["were".to_string(), "we're".to_string(), "where".to_string()],
-                vec!["lose".to_string(), "loose".to_string()],
             ],
         }
     }
 
     pub(crate) fn find_matching_set(&self, word: &str) -> Option<&Vec<String>> {
-        self.sets
-            .iter()
-            .find(|set| set.contains(&word.to_lowercase()))
+        word_sets::find_matching_set(&self.sets, word)
     }
 
     pub(crate) fn get_alternative<R: Rng>(&self, word: &str, rng: &mut R) -> Option<String> {
-        if let Some(set) = self.find_matching_set(word) {
-            let alternatives: Vec<&String> = set
-                .iter()
-                .filter(|w| w.to_lowercase() != word.to_lowercase())
-                .collect();
-
-            if !alternatives.is_empty() {
-                let alt = alternatives.choose(rng).unwrap();
-
-                // Preserve capitalization
-                if word.chars().next().unwrap().is_uppercase() {
-                    let mut alt_chars: Vec<char> = alt.chars().collect();
-                    if !alt_chars.is_empty() {
-                        alt_chars[0] = alt_chars[0].to_uppercase().next().unwrap();
-                    }
-                    return Some(alt_chars.into_iter().collect());
-                }
-
-                return Some(alt.to_string());
-            }
-        }
-        None
+        word_sets::get_alternative(&self.sets, word, rng)
     }
 }
 
@@ -126,14 +105,14 @@ mod tests {
     fn test_get_alternative_single_option() {
         let hs = HomophoneSets::new_for_tests();
         let mut rng = rand::rng();
-        // Test "affect" -> "effect"
-        let alt_affect = hs.get_alternative("affect", &mut rng);
-        assert!(alt_affect.is_some());
-        assert_eq!(alt_affect.unwrap(), "effect");
-
-        // Test "Effect" -> "Affect" (case preserved)
-        let alt_effect_caps = hs.get_alternative("Effect", &mut rng);
-        assert!(alt_effect_caps.is_some());
-        assert_eq!(alt_effect_caps.unwrap(), "Affect");
+        // Test "its" -> "it's"
+        let alt_its = hs.get_alternative("its", &mut rng);
+        assert!(alt_its.is_some());
+        assert_eq!(alt_its.unwrap(), "it's");
+
+        // Test "Whose" -> "Who's" (case preserved)
+        let alt_whose_caps = hs.get_alternative("Whose", &mut rng);
+        assert!(alt_whose_caps.is_some());
+        assert_eq!(alt_whose_caps.unwrap(), "Who's");
     }
 }
@@ -0,0 +1,236 @@
+use crate::span::Span;
+
+/// Elements whose content is never prose, and is skipped up to the matching closing tag.
+const OPAQUE_ELEMENTS: &[&str] = &["script", "style", "code", "pre", "kbd", "samp", "textarea"];
+
+/// Elements which separate blocks of text, so that sentences never run across them.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// A part of an HTML document which isn't text content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Markup {
+    pub(crate) span: Span,
+
+    /// Whether this markup separates blocks of text, like `<p>` or `<br>`.
+    pub(crate) is_break: bool,
+}
+
+/// Finds the markup in an HTML document: tags and their attributes, comments, character
+/// references, and the whole of any `<script>`, `<style>`, `<code>` or similar element.
+///
+/// This is a lenient scan rather than a full parse, so malformed markup is skipped over rather
+/// than rejected. A stray `<` which doesn't start a tag is treated as markup too.
+pub(crate) fn markup(source: &str) -> Vec<Markup> {
+    let lowercase = source.to_ascii_lowercase();
+    let bytes = source.as_bytes();
+    let mut markup = Vec::new();
+    let mut position = 0;
+
+    while let Some(offset) = source[position..].find(['<', '&']) {
+        let start = position + offset;
+
+        if bytes[start] == b'&' {
+            position = match character_reference_end(source, start) {
+                Some(end) => {
+                    markup.push(Markup {
+                        span: Span::new(start, end),
+                        is_break: false,
+                    });
+                    end
+                }
+                None => start + 1,
+            };
+            continue;
+        }
+
+        let (end, is_break) = if lowercase[start..].starts_with("<!--") {
+            let end = lowercase[start + 4..]
+                .find("-->")
+                .map_or(source.len(), |i| start + 4 + i + 3);
+            (end, false)
+        } else {
+            tag_end(source, &lowercase, start)
+        };
+
+        markup.push(Markup {
+            span: Span::new(start, end),
+            is_break,
+        });
+        position = end;
+    }
+
+    markup
+}
+
+/// Finds where the tag starting at `start` ends, along with whether it breaks the text. Opaque
+/// elements extend up to the end of their closing tag.
+fn tag_end(source: &str, lowercase: &str, start: usize) -> (usize, bool) {
+    let rest = &lowercase[start + 1..];
+
+    let name_start = usize::from(rest.starts_with('/'));
+    let name: &str = rest[name_start..]
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+        .unwrap_or_default();
+
+    let is_tag = !name.is_empty() || rest.starts_with('!') || rest.starts_with('?');
+    if !is_tag {
+        return (start + 1, false);
+    }
+
+    // Scan to the closing '>', ignoring any inside quoted attribute values.
+    let mut quote = None;
+    let mut end = source.len();
+    for (i, c) in source[start + 1..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => {
+                end = start + 1 + i + 1;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let is_break = BLOCK_ELEMENTS.contains(&name);
+
+    if name_start == 0 && OPAQUE_ELEMENTS.contains(&name) {
+        let closing = format!("</{name}");
+        if let Some(i) = lowercase[end..].find(&closing) {
+            let close_start = end + i;
+            let close_end = source[close_start..]
+                .find('>')
+                .map_or(source.len(), |i| close_start + i + 1);
+            return (close_end, is_break);
+        }
+    }
+
+    (end, is_break)
+}
+
+/// Finds the end of the character reference starting at `start`, such as `&amp;` or `&#8217;`.
+fn character_reference_end(source: &str, start: usize) -> Option<usize> {
+    let rest = &source[start + 1..];
+    let (body, digits): (&str, fn(char) -> bool) =
+        if let Some(hex) = rest.strip_prefix("#x").or_else(|| rest.strip_prefix("#X")) {
+            (hex, |c| c.is_ascii_hexdigit())
+        } else if let Some(decimal) = rest.strip_prefix('#') {
+            (decimal, |c| c.is_ascii_digit())
+        } else {
+            (rest, |c| c.is_ascii_alphanumeric())
+        };
+
+    let length = body.find(|c: char| !digits(c)).unwrap_or(body.len());
+    let prefix = rest.len() - body.len();
+
+    (length > 0 && body[length..].starts_with(';')).then_some(start + 1 + prefix + length + 1)
+}
+
+/// Replaces the markup in `source` with whitespace, keeping every byte offset the same, so that
+/// the text content can be split into sentences. Markup which breaks the text becomes a
+/// paragraph break.
+pub(crate) fn mask(source: &str, markup: &[Markup]) -> String {
+    let mut masked = String::with_capacity(source.len());
+    let mut position = 0;
+
+    for m in markup {
+        masked.push_str(&source[position..m.span.start]);
+
+        let mut length = m.span.end - m.span.start;
+        if m.is_break && length >= 2 {
+            masked.push_str("\n\n");
+            length -= 2;
+        }
+        masked.extend(std::iter::repeat_n(' ', length));
+
+        position = m.span.end;
+    }
+
+    masked.push_str(&source[position..]);
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markup_text(source: &str) -> Vec<&str> {
+        markup(source)
+            .into_iter()
+            .map(|m| &source[m.span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_tags_and_attributes() {
+        assert_eq!(
+            markup_text(r#"<p class="a>b">Your <a href='x.html'>link</a>.</p>"#),
+            vec![r#"<p class="a>b">"#, "<a href='x.html'>", "</a>", "</p>"]
+        );
+    }
+
+    #[test]
+    fn test_opaque_elements_are_skipped_whole() {
+        assert_eq!(
+            markup_text("A<script>if (a < b) {}</script>B<CODE>x.y</Code>C"),
+            vec!["<script>if (a < b) {}</script>", "<CODE>x.y</Code>"]
+        );
+    }
+
+    #[test]
+    fn test_comments_and_references() {
+        assert_eq!(
+            markup_text("Don&rsquo;t <!-- a > b --> &#8217; &#x2019; & fish &amp chips"),
+            vec!["&rsquo;", "<!-- a > b -->", "&#8217;", "&#x2019;"]
+        );
+    }
+
+    #[test]
+    fn test_stray_angle_bracket() {
+        assert_eq!(markup_text("1 < 2"), vec!["<"]);
+    }
+
+    #[test]
+    fn test_mask_keeps_offsets() {
+        let source = "<h1>Title</h1><p>Café <b>au</b> lait</p>";
+        let masked = mask(source, &markup(source));
+
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(masked, "\n\n  Title\n\n   \n\n Café    au     lait\n\n  ");
+    }
+}
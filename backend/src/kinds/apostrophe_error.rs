@@ -0,0 +1,236 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::span::Span;
+
+/// Words which are usually followed by a plural noun, or an adjective describing one.
+const PLURAL_CUES: &[&str] = &[
+    "all", "both", "eight", "five", "four", "few", "her", "his", "many", "my", "nine", "no", "our",
+    "several", "seven", "six", "some", "ten", "the", "their", "these", "those", "three", "two",
+    "your",
+];
+
+/// Words ending in "s" which aren't plurals, even after a plural cue.
+const NOT_PLURAL: &[&str] = &["always", "means", "news", "perhaps", "series", "species"];
+
+/// Words which form contractions with "'s", as in "it's" or "that's", rather than possessives.
+const CONTRACTED: &[&str] = &[
+    "he", "here", "how", "it", "let", "she", "that", "there", "what", "when", "where", "who", "why",
+];
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
+}
+
+/// The parts of a possessive such as "dog's" or "dogs'": the word before the apostrophe, the
+/// apostrophe itself, and whether it comes after the final "s".
+fn possessive(word: &str) -> Option<(&str, char, bool)> {
+    let (stem, apostrophe, after_s) = if let Some(stem) = word
+        .strip_suffix(['s', 'S'])
+        .and_then(|rest| rest.strip_suffix(is_apostrophe))
+    {
+        (stem, word[stem.len()..].chars().next()?, false)
+    } else {
+        let stem = word.strip_suffix(is_apostrophe)?;
+        (stem, word[stem.len()..].chars().next()?, true)
+    };
+
+    let is_possessive = stem.chars().count() >= 2
+        && stem.chars().all(char::is_alphabetic)
+        && (after_s || !CONTRACTED.contains(&stem.to_lowercase().as_str()))
+        && (!after_s || stem.ends_with(['s', 'S']));
+
+    is_possessive.then_some((stem, apostrophe, after_s))
+}
+
+/// Whether `word` looks like a regular plural, such as "apples", which could take a
+/// greengrocer's apostrophe.
+fn is_plural(word: &str) -> bool {
+    word.chars().count() >= 4
+        && word.chars().all(|c| c.is_alphabetic() && c.is_lowercase())
+        && word.ends_with('s')
+        && !["ss", "us", "is"]
+            .iter()
+            .any(|ending| word.ends_with(ending))
+        && !NOT_PLURAL.contains(&word)
+}
+
+/// Splits `text` into words which may contain apostrophes, including a trailing one after an
+/// "s" as in "the dogs' bowls". Leading apostrophes are treated as opening quotes, and a word
+/// which opens with one is assumed to close with one too.
+fn tokens(text: &str) -> Vec<Span> {
+    let is_word_char = |c: char| c.is_alphabetic() || is_apostrophe(c);
+
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if !is_word_char(c) {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some((i, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+            end = i + c.len_utf8();
+        }
+
+        let token = &text[start..end];
+        let leading = token.len() - token.trim_start_matches(is_apostrophe).len();
+        let word = token[leading..].trim_end_matches(is_apostrophe);
+        if word.is_empty() {
+            continue;
+        }
+
+        let start = start + leading;
+        let mut word_end = start + word.len();
+        if leading == 0 && word.ends_with(['s', 'S']) && word_end < end {
+            word_end += text[word_end..].chars().next().map_or(0, char::len_utf8);
+        }
+
+        tokens.push(Span::new(start, word_end));
+    }
+
+    tokens
+}
+
+/// Misuses an apostrophe: adds a greengrocer's apostrophe to a plural ("apple's"), drops a
+/// possessive one ("the dogs bone"), or moves it to the wrong side of the "s" ("the childrens'
+/// toys").
+///
+/// Plurals are recognised by a small heuristic: a lowercase word ending in "s", but not "ss",
+/// "us" or "is", directly after a word such as "the", "some" or "two".
+pub(crate) struct ApostropheError;
+
+impl ApostropheError {
+    pub(crate) const NAME: &'static str = "ApostropheError";
+}
+
+impl MutationKind for ApostropheError {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds an apostrophe to a plural, or drops or moves a possessive one."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let tokens = tokens(text);
+
+        tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, token)| {
+                let word = &text[token.range()];
+                if possessive(word).is_some() {
+                    return true;
+                }
+
+                let Some(previous) = i.checked_sub(1).map(|i| tokens[i]) else {
+                    return false;
+                };
+                let cue = text[previous.range()].to_lowercase();
+
+                text[previous.end..token.start]
+                    .chars()
+                    .all(char::is_whitespace)
+                    && PLURAL_CUES.contains(&cue.as_str())
+                    && is_plural(word)
+            })
+            .map(|(_, token)| *token)
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+
+        let replacement = match possessive(word) {
+            Some(_) if rng.random_bool(0.5) => word.replace(is_apostrophe, ""),
+            Some((stem, apostrophe, true)) => {
+                let (singular, s) = stem.split_at(stem.len() - 1);
+                format!("{singular}{apostrophe}{s}")
+            }
+            Some((stem, apostrophe, false)) => {
+                let s = word[stem.len()..].trim_matches(is_apostrophe);
+                format!("{stem}{s}{apostrophe}")
+            }
+            None if is_plural(word) => {
+                let (singular, s) = word.split_at(word.len() - 1);
+                format!("{singular}'{s}")
+            }
+            None => return None,
+        };
+
+        trace!("Misusing an apostrophe in '{word}'");
+
+        Some(replacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        ApostropheError
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        let text = "The dogs' bone, 'dogs' and the dog’s";
+        let tokens: Vec<&str> = tokens(text).into_iter().map(|s| &text[s.range()]).collect();
+        assert_eq!(
+            tokens,
+            vec!["The", "dogs'", "bone", "dogs", "and", "the", "dog’s"]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("The children's toys and two apples. It's his glass, the news and my bus."),
+            vec!["children's", "apples"]
+        );
+        assert_eq!(
+            candidates("The cats' bowls. That's Anna’s, she runs."),
+            vec!["cats'", "Anna’s"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut apply = |word: &str| {
+            ApostropheError
+                .apply(word, Span::new(0, word.len()), &mut rng)
+                .unwrap()
+        };
+
+        assert_eq!(apply("apples"), "apple's");
+        for _ in 0..10 {
+            assert!(["dogs", "dogs'"].contains(&apply("dog's").as_str()));
+            assert!(["dogs", "dog's"].contains(&apply("dogs'").as_str()));
+            assert!(["Annas", "Annas’"].contains(&apply("Anna’s").as_str()));
+        }
+    }
+
+    #[test]
+    fn test_apply_rejects_other_words() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            ApostropheError.apply("it's", Span::new(0, 4), &mut rng),
+            None
+        );
+        assert_eq!(
+            ApostropheError.apply("glass", Span::new(0, 5), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{DETERMINERS, MutationKind, is_noun_like, words};
+use crate::case::capitalize_first;
+use crate::span::Span;
+
+/// Capitalizes a common noun in the middle of a sentence, as in "the Weather".
+///
+/// Nouns are recognised by a simple heuristic: a lowercase word of three or more letters, other
+/// than a pronoun, auxiliary verb or similar, which directly follows a determiner such as "the"
+/// or "my".
+pub(crate) struct CapitalizeNoun;
+
+impl CapitalizeNoun {
+    pub(crate) const NAME: &'static str = "CapitalizeNoun";
+}
+
+impl MutationKind for CapitalizeNoun {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Capitalizes a common noun in the middle of a sentence."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let (determiner, noun) = (&text[pair[0].range()], &text[pair[1].range()]);
+                let gap = &text[pair[0].end..pair[1].start];
+
+                gap.chars().all(char::is_whitespace)
+                    && DETERMINERS.contains(&determiner.to_lowercase().as_str())
+                    && is_noun_like(noun)
+            })
+            .map(|pair| pair[1])
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+
+        if !word.starts_with(char::is_lowercase) {
+            return None;
+        }
+
+        trace!("Capitalizing '{}' at byte {}", word, span.start);
+
+        Some(capitalize_first(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        CapitalizeNoun
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("The weather in my town, a ox, the NHS and their café's menu."),
+            vec!["weather", "town", "café's"]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates_needs_adjacent_determiner() {
+        assert!(candidates("the, weather").is_empty());
+        assert!(candidates("weather town").is_empty());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            CapitalizeNoun.apply("the weather", Span::new(4, 11), &mut rng),
+            Some("Weather".to_string())
+        );
+        assert_eq!(
+            CapitalizeNoun.apply("Weather", Span::new(0, 7), &mut rng),
+            None
+        );
+    }
+}
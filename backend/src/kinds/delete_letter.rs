@@ -0,0 +1,113 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, letter_runs};
+use crate::span::Span;
+
+/// Deletes a letter from within a word, as in "th" for "the" or "occured" for "occurred".
+pub(crate) struct DeleteLetter;
+
+impl DeleteLetter {
+    pub(crate) const NAME: &'static str = "DeleteLetter";
+
+    /// Words shorter than this become a different short word, or vanish, when a letter is lost.
+    const MIN_WORD_LENGTH: usize = 3;
+}
+
+impl MutationKind for DeleteLetter {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Deletes a single letter from within a word."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let mut candidates = Vec::new();
+
+        for word in letter_runs(text) {
+            if word.len() < Self::MIN_WORD_LENGTH {
+                continue;
+            }
+
+            // A dropped first letter rarely looks like a slip, so only later letters are
+            // candidates. Both letters of a doubled pair are, though, which makes dropping one of
+            // them twice as likely as dropping any other letter.
+            for (i, &(position, c)) in word.iter().enumerate() {
+                let doubled = word.get(i + 1).is_some_and(|&(_, next)| next == c);
+
+                if i > 0 || doubled {
+                    candidates.push(Span::new(position, position + c.len_utf8()));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let mut chars = text.get(span.range())?.chars();
+
+        let (Some(letter), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+
+        if !letter.is_alphabetic() {
+            return None;
+        }
+
+        trace!("Deleting letter '{}' at byte {}", letter, span.start);
+
+        Some(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        DeleteLetter
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates_skips_first_letter_and_short_words() {
+        assert_eq!(candidates("the ox, é"), vec!["h", "e"]);
+    }
+
+    #[test]
+    fn test_find_candidates_favours_doubled_letters() {
+        let text = "all, ssh";
+        assert_eq!(
+            DeleteLetter.find_candidates(text),
+            vec![
+                Span::new(1, 2),
+                Span::new(2, 3),
+                Span::new(5, 6),
+                Span::new(6, 7),
+                Span::new(7, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates_non_ascii() {
+        assert_eq!(candidates("café"), vec!["a", "f", "é"]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            DeleteLetter.apply("café", Span::new(3, 5), &mut rng),
+            Some(String::new())
+        );
+        assert_eq!(DeleteLetter.apply("a.b", Span::new(1, 2), &mut rng), None);
+    }
+}
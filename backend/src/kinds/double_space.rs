@@ -0,0 +1,81 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::span::Span;
+
+/// Doubles the space between two words, as in "a  lot".
+///
+/// The span covers the words on either side, so that the change can be highlighted.
+pub(crate) struct DoubleSpace;
+
+impl DoubleSpace {
+    pub(crate) const NAME: &'static str = "DoubleSpace";
+}
+
+impl MutationKind for DoubleSpace {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Types two spaces where there should be one."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let gap = &text[pair[0].end..pair[1].start];
+                gap.ends_with(' ') && gap.matches(char::is_whitespace).count() == 1
+            })
+            .map(|pair| Span::new(pair[0].start, pair[1].end))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let source = text.get(span.range())?;
+
+        if source.matches(char::is_whitespace).count() != 1 {
+            return None;
+        }
+
+        let (before, after) = source.split_once(' ')?;
+
+        trace!("Doubling the space in '{source}'");
+
+        Some(format!("{before}  {after}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        DoubleSpace
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("A lot, of  this\nthat. Café au"),
+            vec!["A lot", "lot, of", "that. Café", "Café au"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            DoubleSpace.apply("Yes, sir", Span::new(0, 8), &mut rng),
+            Some("Yes,  sir".to_string())
+        );
+        assert_eq!(DoubleSpace.apply("a\tb", Span::new(0, 3), &mut rng), None);
+    }
+}
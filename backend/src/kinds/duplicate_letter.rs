@@ -0,0 +1,111 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, letter_runs};
+use crate::span::Span;
+
+/// Types a consonant twice, as in "thhe" for "the" or "untill" for "until".
+pub(crate) struct DuplicateLetter;
+
+impl DuplicateLetter {
+    pub(crate) const NAME: &'static str = "DuplicateLetter";
+
+    /// Doubling a letter in a very short word tends to make another real word, like "off".
+    const MIN_WORD_LENGTH: usize = 3;
+}
+
+/// Whether doubling `c` would look like a genuine slip. Doubled consonants are the common
+/// mistake, while doubled vowels tend to stand out.
+fn is_doubling_candidate(c: char) -> bool {
+    c.is_alphabetic() && !matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+impl MutationKind for DuplicateLetter {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Types a consonant within a word twice."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let mut candidates = Vec::new();
+
+        for word in letter_runs(text) {
+            if word.len() < Self::MIN_WORD_LENGTH {
+                continue;
+            }
+
+            // Skip the first letter, and letters which are already doubled, since tripled
+            // letters don't look like a genuine slip.
+            for (i, &(position, c)) in word.iter().enumerate().skip(1) {
+                let beside_same = word[i - 1].1 == c || word.get(i + 1).is_some_and(|w| w.1 == c);
+
+                if is_doubling_candidate(c) && !beside_same {
+                    candidates.push(Span::new(position, position + c.len_utf8()));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let mut chars = text.get(span.range())?.chars();
+
+        let (Some(letter), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+
+        if !is_doubling_candidate(letter) {
+            return None;
+        }
+
+        trace!("Duplicating letter '{}' at byte {}", letter, span.start);
+
+        Some([letter, letter].iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        DuplicateLetter
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates_only_interior_consonants() {
+        assert_eq!(candidates("The ox until"), vec!["h", "n", "t", "l"]);
+    }
+
+    #[test]
+    fn test_find_candidates_skips_doubled_letters() {
+        assert_eq!(candidates("occured"), vec!["r", "d"]);
+    }
+
+    #[test]
+    fn test_find_candidates_non_ascii() {
+        assert_eq!(candidates("façade"), vec!["ç", "d"]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            DuplicateLetter.apply("façade", Span::new(2, 4), &mut rng),
+            Some("çç".to_string())
+        );
+        assert_eq!(
+            DuplicateLetter.apply("bee", Span::new(1, 2), &mut rng),
+            None
+        );
+    }
+}
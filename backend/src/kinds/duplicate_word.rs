@@ -0,0 +1,120 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::case::{is_capitalized, lowercase_first};
+use crate::span::Span;
+
+/// Repeats a word, as in "the the", which is easy to read past, especially across a line break.
+///
+/// The duplicate is inserted in front of the following word, so its span covers just the
+/// inserted copy and the space after it.
+pub(crate) struct DuplicateWord;
+
+impl DuplicateWord {
+    pub(crate) const NAME: &'static str = "DuplicateWord";
+}
+
+/// Whether `c` can be part of a word.
+fn is_word_char(c: char) -> bool {
+    c.is_alphabetic() || c == '\''
+}
+
+impl MutationKind for DuplicateWord {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Repeats a word, as in 'the the'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let gap = &text[pair[0].end..pair[1].start];
+                !gap.is_empty() && gap.chars().all(char::is_whitespace)
+            })
+            .map(|pair| Span::new(pair[1].start, pair[1].start))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        if span.start != span.end || !text.get(span.start..)?.starts_with(char::is_alphabetic) {
+            return None;
+        }
+
+        let before = text.get(..span.start)?;
+        let previous = before.trim_end();
+        if previous.len() == before.len() {
+            return None;
+        }
+
+        let word_start = previous
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map(|(i, _)| i)?;
+        let word = previous[word_start..].trim_start_matches('\'');
+
+        if word.is_empty() {
+            return None;
+        }
+
+        // A word capitalised only because it starts a sentence is repeated in lowercase.
+        let starts_sentence = previous[..previous.len() - word.len()]
+            .trim_end()
+            .chars()
+            .next_back()
+            .is_none_or(|c| matches!(c, '.' | '!' | '?'));
+
+        let duplicate = if starts_sentence && is_capitalized(word) {
+            lowercase_first(word)
+        } else {
+            word.to_string()
+        };
+
+        trace!("Repeating '{}' at byte {}", duplicate, span.start);
+
+        Some(format!("{duplicate} "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_find_candidates() {
+        // Words separated by anything but whitespace are left alone.
+        let candidates = DuplicateWord.find_candidates("Go to\nthe 'shop', now.");
+        assert_eq!(candidates, vec![Span::new(3, 3), Span::new(6, 6)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let text = "Go to\nthe shop.";
+        assert_eq!(
+            DuplicateWord.apply(text, Span::new(6, 6), &mut rng),
+            Some("to ".to_string())
+        );
+        assert_eq!(DuplicateWord.apply(text, Span::new(7, 7), &mut rng), None);
+    }
+
+    #[test]
+    fn test_apply_lowercases_sentence_starts() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let text = "The cat. NASA said Paris is. Don't go.";
+        let duplicate =
+            |at: usize, rng: &mut StdRng| DuplicateWord.apply(text, Span::new(at, at), rng);
+
+        assert_eq!(duplicate(4, &mut rng), Some("the ".to_string()));
+        assert_eq!(duplicate(14, &mut rng), Some("NASA ".to_string()));
+        assert_eq!(duplicate(25, &mut rng), Some("Paris ".to_string()));
+        assert_eq!(duplicate(35, &mut rng), Some("don't ".to_string()));
+    }
+}
@@ -0,0 +1,78 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{DETERMINERS, MutationKind, words};
+use crate::span::Span;
+
+/// Inserts a stray comma between two words, as in "The dog, barked."
+///
+/// Commas are only inserted between words separated by a single space, before a lowercase word,
+/// and never straight after a determiner such as "the".
+pub(crate) struct InsertPunctuation;
+
+impl InsertPunctuation {
+    pub(crate) const NAME: &'static str = "InsertPunctuation";
+}
+
+impl MutationKind for InsertPunctuation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Inserts a stray comma between two words."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let before = &text[pair[0].range()];
+
+                &text[pair[0].end..pair[1].start] == " "
+                    && text[pair[1].start..].starts_with(char::is_lowercase)
+                    && !DETERMINERS.contains(&before.to_lowercase().as_str())
+            })
+            .map(|pair| Span::new(pair[0].end, pair[0].end))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let between_words = span.start == span.end
+            && text.get(..span.start)?.ends_with(char::is_alphabetic)
+            && text.get(span.start..)?.starts_with(' ');
+
+        if !between_words {
+            return None;
+        }
+
+        trace!("Inserting a comma at byte {}", span.start);
+
+        Some(",".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_find_candidates() {
+        let candidates = InsertPunctuation.find_candidates("The dog barked at  the cat, then Sam.");
+        assert_eq!(candidates, vec![Span::new(7, 7), Span::new(14, 14)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            InsertPunctuation.apply("dog barked", Span::new(3, 3), &mut rng),
+            Some(",".to_string())
+        );
+        assert_eq!(
+            InsertPunctuation.apply("dog, barked", Span::new(4, 4), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::span::Span;
+
+/// Removes the space between two words, as in "alot" for "a lot".
+///
+/// The span covers both words, so that the change can be highlighted.
+pub(crate) struct JoinWords;
+
+impl JoinWords {
+    pub(crate) const NAME: &'static str = "JoinWords";
+
+    /// Longer joined words stop looking like a plausible single word.
+    const MAX_JOINED_LENGTH: usize = 12;
+}
+
+/// Whether `word` is made only of lowercase letters.
+fn is_plain(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_alphabetic() && c.is_lowercase())
+}
+
+impl MutationKind for JoinWords {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Joins two words by removing the space between them."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let (first, second) = (&text[pair[0].range()], &text[pair[1].range()]);
+
+                &text[pair[0].end..pair[1].start] == " "
+                    && is_plain(first)
+                    && is_plain(second)
+                    && first.chars().count() + second.chars().count() <= Self::MAX_JOINED_LENGTH
+            })
+            .map(|pair| Span::new(pair[0].start, pair[1].end))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let source = text.get(span.range())?;
+        let (first, second) = source.split_once(' ')?;
+
+        if !is_plain(first) || !is_plain(second) {
+            return None;
+        }
+
+        trace!("Joining '{first}' and '{second}'");
+
+        Some(format!("{first}{second}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        JoinWords
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("a lot, of  Time can't pass here extraordinarily"),
+            vec!["a lot", "pass here"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            JoinWords.apply("a lot", Span::new(0, 5), &mut rng),
+            Some("alot".to_string())
+        );
+        assert_eq!(JoinWords.apply("a, lot", Span::new(0, 6), &mut rng), None);
+    }
+}
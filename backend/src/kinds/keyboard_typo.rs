@@ -0,0 +1,201 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::models::{KeyboardLayout, MutationRequestOptions};
+use crate::span::Span;
+
+/// How far each row of letter keys is shifted to the right of the row above, in key widths,
+/// starting from the top letter row.
+const ROW_OFFSETS: [f32; 3] = [0.0, 0.25, 0.75];
+
+/// The top, home and bottom rows of keys on a layout, starting from the leftmost key which can
+/// hold a letter. Punctuation keys are included so that every key sits in the right column.
+fn rows(layout: KeyboardLayout) -> [&'static str; 3] {
+    match layout {
+        KeyboardLayout::Qwerty => ["qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"],
+        KeyboardLayout::Qwertz => ["qwertzuiopü+", "asdfghjklöä#", "yxcvbnm,.-"],
+        KeyboardLayout::Azerty => ["azertyuiop^$", "qsdfghjklmù*", "wxcvbn,;:!"],
+        KeyboardLayout::Dvorak => ["',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"],
+    }
+}
+
+/// Maps every letter on `layout` to the letters on the keys around it.
+fn neighbours(layout: KeyboardLayout) -> HashMap<char, Vec<char>> {
+    let keys: Vec<(char, usize, usize, f32)> = rows(layout)
+        .iter()
+        .zip(ROW_OFFSETS)
+        .enumerate()
+        .flat_map(|(row, (keys, offset))| {
+            keys.chars().enumerate().map(move |(column, key)| {
+                #[allow(clippy::cast_precision_loss)]
+                (key, row, column, column as f32 + offset)
+            })
+        })
+        .collect();
+
+    let mut neighbours = HashMap::new();
+
+    for &(key, row, column, x) in keys.iter().filter(|k| k.0.is_alphabetic()) {
+        let adjacent: Vec<char> = keys
+            .iter()
+            .filter(|&&(other, other_row, other_column, other_x)| {
+                let same_row = other_row == row && other_column.abs_diff(column) == 1;
+                let next_row = other_row.abs_diff(row) == 1 && (other_x - x).abs() < 1.0;
+
+                other.is_alphabetic() && (same_row || next_row)
+            })
+            .map(|&(other, ..)| other)
+            .collect();
+
+        neighbours.insert(key, adjacent);
+    }
+
+    neighbours
+}
+
+/// Replaces a letter with one on a neighbouring key, like hitting the wrong key while typing.
+pub(crate) struct KeyboardTypo {
+    layout: KeyboardLayout,
+
+    /// The letters on the keys around each letter of the layout, in lowercase.
+    neighbours: HashMap<char, Vec<char>>,
+}
+
+impl KeyboardTypo {
+    pub(crate) const NAME: &'static str = "KeyboardTypo";
+
+    pub(crate) fn new(layout: KeyboardLayout) -> Self {
+        KeyboardTypo {
+            layout,
+            neighbours: neighbours(layout),
+        }
+    }
+
+    /// The letters next to `letter` on the keyboard, or `None` if it isn't on the layout.
+    fn neighbours_of(&self, letter: char) -> Option<&[char]> {
+        let mut lowercase = letter.to_lowercase();
+
+        let (Some(key), None) = (lowercase.next(), lowercase.next()) else {
+            return None;
+        };
+
+        self.neighbours
+            .get(&key)
+            .map(Vec::as_slice)
+            .filter(|n| !n.is_empty())
+    }
+}
+
+impl MutationKind for KeyboardTypo {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces a letter with one on a neighbouring key."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        text.char_indices()
+            .filter(|&(_, c)| c.is_alphabetic() && self.neighbours_of(c).is_some())
+            .map(|(i, c)| Span::new(i, i + c.len_utf8()))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let mut chars = text.get(span.range())?.chars();
+
+        let (Some(letter), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+
+        let &typo = self.neighbours_of(letter)?.choose(rng)?;
+
+        trace!(
+            "Replacing '{}' with neighbouring key '{}' at byte {}",
+            letter, typo, span.start
+        );
+
+        if letter.is_uppercase() {
+            Some(typo.to_uppercase().collect())
+        } else {
+            Some(typo.to_string())
+        }
+    }
+
+    fn configure(&self, options: &MutationRequestOptions) -> Option<Arc<dyn MutationKind>> {
+        (options.keyboard_layout != self.layout)
+            .then(|| Arc::new(KeyboardTypo::new(options.keyboard_layout)) as Arc<dyn MutationKind>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn sorted_neighbours(layout: KeyboardLayout, letter: char) -> String {
+        let mut keys = KeyboardTypo::new(layout)
+            .neighbours_of(letter)
+            .unwrap()
+            .to_vec();
+        keys.sort_unstable();
+        keys.into_iter().collect()
+    }
+
+    #[test]
+    fn test_qwerty_neighbours() {
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwerty, 'g'), "bfhtvy");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwerty, 'e'), "drsw");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwerty, 'a'), "qswz");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwerty, 'P'), "lo");
+    }
+
+    #[test]
+    fn test_other_layouts() {
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwertz, 'z'), "ghtu");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Qwertz, 'ö'), "lpäü");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Azerty, 'a'), "qz");
+        assert_eq!(sorted_neighbours(KeyboardLayout::Dvorak, 'e'), "jopqu");
+    }
+
+    #[test]
+    fn test_find_candidates_skips_keys_off_the_layout() {
+        let typo = KeyboardTypo::new(KeyboardLayout::Qwerty);
+        assert_eq!(typo.find_candidates("Só 1!"), vec![Span::new(0, 1)]);
+    }
+
+    #[test]
+    fn test_apply_preserves_case() {
+        let typo = KeyboardTypo::new(KeyboardLayout::Qwerty);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            let replacement = typo.apply("The", Span::new(0, 1), &mut rng).unwrap();
+            assert!(["R", "Y", "F", "G"].contains(&replacement.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_configure_switches_layout() {
+        let typo = KeyboardTypo::new(KeyboardLayout::Qwerty);
+        assert!(typo.configure(&MutationRequestOptions::default()).is_none());
+
+        let options = MutationRequestOptions {
+            keyboard_layout: KeyboardLayout::Azerty,
+            ..Default::default()
+        };
+        let azerty = typo.configure(&options).unwrap();
+        assert_eq!(azerty.find_candidates("q"), vec![Span::new(0, 1)]);
+        let mut rng = StdRng::seed_from_u64(42);
+        let replacement = azerty.apply("m", Span::new(0, 1), &mut rng).unwrap();
+        assert!(
+            ["l", "p", "ù"].contains(&replacement.as_str()),
+            "{replacement}"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, sentence_first_words, words};
+use crate::case::{is_capitalized, lowercase_first};
+use crate::span::Span;
+
+/// Lowercases a proper noun or the pronoun "I", as in "i went to paris".
+///
+/// Proper nouns are recognised by a simple heuristic: a capitalized word which doesn't start a
+/// sentence or a quotation.
+pub(crate) struct LowercaseProperNoun;
+
+impl LowercaseProperNoun {
+    pub(crate) const NAME: &'static str = "LowercaseProperNoun";
+}
+
+impl MutationKind for LowercaseProperNoun {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Lowercases a proper noun or the word 'I'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let first_words = sentence_first_words(text);
+
+        words(text)
+            .into_iter()
+            .filter(|span| {
+                let word = &text[span.range()];
+                let is_pronoun = word == "I" || word.starts_with("I'");
+
+                let opens_quote = text[..span.start]
+                    .trim_end()
+                    .ends_with(['"', '“', '‘', '\'', '(', '«']);
+
+                (is_pronoun || first_words.binary_search(span).is_err() && !opens_quote)
+                    && is_capitalized(word)
+            })
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+
+        if !is_capitalized(word) {
+            return None;
+        }
+
+        trace!("Lowercasing proper noun '{}' at byte {}", word, span.start);
+
+        Some(lowercase_first(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        LowercaseProperNoun
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("Then I'm off to Paris with NASA. I said \"Hello\" to Zoë."),
+            vec!["I'm", "Paris", "I", "Zoë"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            LowercaseProperNoun.apply("I'm", Span::new(0, 3), &mut rng),
+            Some("i'm".to_string())
+        );
+        assert_eq!(
+            LowercaseProperNoun.apply("NASA", Span::new(0, 4), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, sentence_first_words};
+use crate::case::{is_capitalized, lowercase_first};
+use crate::span::Span;
+
+/// Lowercases the first letter of a sentence, as in "the cat sat."
+pub(crate) struct LowercaseSentenceStart;
+
+impl LowercaseSentenceStart {
+    pub(crate) const NAME: &'static str = "LowercaseSentenceStart";
+}
+
+impl MutationKind for LowercaseSentenceStart {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Lowercases the first letter of a sentence."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        // Acronyms such as "NASA" are left alone, since "nASA" doesn't look like a slip.
+        sentence_first_words(text)
+            .into_iter()
+            .filter(|span| is_capitalized(&text[span.range()]))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+
+        if !is_capitalized(word) {
+            return None;
+        }
+
+        trace!(
+            "Lowercasing sentence start '{}' at byte {}",
+            word, span.start
+        );
+
+        Some(lowercase_first(word))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        LowercaseSentenceStart
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("The cat sat. \"Where?\" I asked. NASA knows.\n\n(Even Paris.) 2 more."),
+            vec!["The", "Where", "I", "Even"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            LowercaseSentenceStart.apply("Élan.", Span::new(0, 5), &mut rng),
+            Some("élan".to_string())
+        );
+        assert_eq!(
+            LowercaseSentenceStart.apply("élan.", Span::new(0, 5), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,301 @@
+mod apostrophe_error;
+mod capitalize_noun;
+mod delete_letter;
+mod double_space;
+mod duplicate_letter;
+mod duplicate_word;
+mod insert_punctuation;
+mod join_words;
+mod keyboard_typo;
+mod lowercase_proper_noun;
+mod lowercase_sentence_start;
+mod numeric_error;
+mod omit_word;
+mod remove_punctuation;
+mod remove_sentence_space;
+mod replace_confusable;
+mod replace_determiner;
+mod replace_homophone;
+mod split_compound;
+mod substitute_punctuation;
+mod swap_letters;
+
+use rand::rngs::StdRng;
+use std::sync::Arc;
+
+use crate::confusables::ConfusableSets;
+use crate::homophones::HomophoneSets;
+use crate::models::{KeyboardLayout, MutationRequestOptions, ProtectionOptions};
+use crate::sentences::split_sentences;
+use crate::span::Span;
+
+pub(crate) use apostrophe_error::ApostropheError;
+pub(crate) use capitalize_noun::CapitalizeNoun;
+pub(crate) use delete_letter::DeleteLetter;
+pub(crate) use double_space::DoubleSpace;
+pub(crate) use duplicate_letter::DuplicateLetter;
+pub(crate) use duplicate_word::DuplicateWord;
+pub(crate) use insert_punctuation::InsertPunctuation;
+pub(crate) use join_words::JoinWords;
+pub(crate) use keyboard_typo::KeyboardTypo;
+pub(crate) use lowercase_proper_noun::LowercaseProperNoun;
+pub(crate) use lowercase_sentence_start::LowercaseSentenceStart;
+pub(crate) use numeric_error::NumericError;
+pub(crate) use omit_word::OmitWord;
+pub(crate) use remove_punctuation::RemovePunctuation;
+pub(crate) use remove_sentence_space::RemoveSentenceSpace;
+pub(crate) use replace_confusable::ReplaceConfusable;
+pub(crate) use replace_determiner::ReplaceDeterminer;
+pub(crate) use replace_homophone::ReplaceHomophone;
+pub(crate) use split_compound::SplitCompound;
+pub(crate) use substitute_punctuation::SubstitutePunctuation;
+pub(crate) use swap_letters::SwapLetters;
+
+/// A type of error that can be introduced into text.
+///
+/// To add a new kind, implement this trait in its own module and register it in
+/// [`MutationRegistry::with_defaults`]; it is then exposed by name through the API.
+pub(crate) trait MutationKind: Send + Sync {
+    /// The identifier for this kind in requests and responses, e.g. `SwapLetters`.
+    fn name(&self) -> &'static str;
+
+    /// A short, human-readable explanation of what this kind does.
+    fn description(&self) -> &'static str;
+
+    /// Finds every span of `text` which this kind could replace.
+    fn find_candidates(&self, text: &str) -> Vec<Span>;
+
+    /// Produces the replacement for the text covered by `span`,
+    /// or `None` if the span no longer admits this kind of mutation.
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String>;
+
+    /// Returns a copy of this kind adapted to a request's settings, or `None` to use the kind as
+    /// registered. Only kinds with per-request settings need to override this.
+    fn configure(&self, _options: &MutationRequestOptions) -> Option<Arc<dyn MutationKind>> {
+        None
+    }
+
+    /// The protection which applies to this kind's candidates, given the request's. Only kinds
+    /// whose whole purpose is to mutate something protected by default need to override this.
+    fn protection(&self, options: ProtectionOptions) -> ProtectionOptions {
+        options
+    }
+}
+
+/// Words which are usually followed by a noun, or an adjective describing one.
+const DETERMINERS: &[&str] = &[
+    "a", "an", "any", "each", "every", "her", "his", "its", "my", "no", "our", "some", "that",
+    "the", "their", "these", "this", "those", "your",
+];
+
+/// Common words of three or more letters which are never nouns, such as pronouns, auxiliary verbs
+/// and conjunctions.
+const NOT_NOUNS: &[&str] = &[
+    "about", "after", "also", "and", "are", "because", "been", "before", "being", "but", "can",
+    "could", "did", "does", "for", "from", "had", "has", "have", "her", "him", "his", "into",
+    "its", "just", "may", "might", "more", "most", "must", "nor", "not", "only", "our", "over",
+    "said", "she", "should", "than", "that", "the", "their", "them", "then", "these", "they",
+    "this", "those", "though", "under", "very", "was", "were", "what", "when", "where", "which",
+    "while", "who", "will", "with", "would", "yet", "you", "your",
+];
+
+/// Whether `word` could be a common noun: a lowercase word of three or more letters which isn't
+/// a pronoun, auxiliary verb or similar.
+fn is_noun_like(word: &str) -> bool {
+    word.chars().count() >= 3
+        && word.starts_with(char::is_lowercase)
+        && !word.chars().any(char::is_uppercase)
+        && !NOT_NOUNS.contains(&word)
+}
+
+/// Splits `text` into its runs of letters, giving the byte offset of each letter.
+fn letter_runs(text: &str) -> Vec<Vec<(usize, char)>> {
+    let mut runs = Vec::new();
+    let mut run = Vec::new();
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphabetic() {
+            run.push((i, c));
+        } else if !run.is_empty() {
+            runs.push(std::mem::take(&mut run));
+        }
+    }
+
+    if !run.is_empty() {
+        runs.push(run);
+    }
+
+    runs
+}
+
+/// Splits `text` into words, made of letters and apostrophes, returning the span of each one.
+/// Quotation marks around a word aren't part of it.
+fn words(text: &str) -> Vec<Span> {
+    let is_word_char = |c: char| c.is_alphabetic() || c == '\'';
+
+    let mut words = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if !is_word_char(c) {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some((i, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+            end = i + c.len_utf8();
+        }
+
+        let token = &text[start..end];
+        let start = start + token.len() - token.trim_start_matches('\'').len();
+        let word = token.trim_matches('\'');
+
+        if !word.is_empty() {
+            words.push(Span::new(start, start + word.len()));
+        }
+    }
+
+    words
+}
+
+/// Finds the first word of each sentence in `text`, for sentences which begin with a word
+/// rather than a number, possibly after an opening quote or bracket. The spans are in order, so
+/// they can be binary searched.
+fn sentence_first_words(text: &str) -> Vec<Span> {
+    split_sentences(text)
+        .into_iter()
+        .filter_map(|sentence| {
+            let first = *words(&text[sentence.clone()]).first()?;
+            let first = Span::new(sentence.start + first.start, sentence.start + first.end);
+
+            let opening = &text[sentence.start..first.start];
+            (!opening.chars().any(char::is_alphanumeric)).then_some(first)
+        })
+        .collect()
+}
+
+/// The set of mutation kinds known to the application.
+#[derive(Default)]
+pub(crate) struct MutationRegistry {
+    kinds: Vec<Arc<dyn MutationKind>>,
+}
+
+impl MutationRegistry {
+    /// Creates a registry containing every built-in mutation kind.
+    pub(crate) fn with_defaults(
+        homophones: Arc<HomophoneSets>,
+        confusables: Arc<ConfusableSets>,
+    ) -> Self {
+        let mut registry = MutationRegistry::default();
+        registry.register(SwapLetters);
+        registry.register(RemovePunctuation);
+        registry.register(ReplaceHomophone::new(homophones));
+        registry.register(DeleteLetter);
+        registry.register(DuplicateLetter);
+        registry.register(KeyboardTypo::new(KeyboardLayout::default()));
+        registry.register(DuplicateWord);
+        registry.register(OmitWord::default());
+        registry.register(LowercaseSentenceStart);
+        registry.register(CapitalizeNoun);
+        registry.register(LowercaseProperNoun);
+        registry.register(SubstitutePunctuation);
+        registry.register(InsertPunctuation);
+        registry.register(DoubleSpace);
+        registry.register(RemoveSentenceSpace);
+        registry.register(JoinWords);
+        registry.register(SplitCompound);
+        registry.register(ApostropheError);
+        registry.register(ReplaceDeterminer);
+        registry.register(NumericError);
+        registry.register(ReplaceConfusable::new(confusables));
+        registry
+    }
+
+    pub(crate) fn register(&mut self, kind: impl MutationKind + 'static) {
+        debug_assert!(
+            self.get(kind.name()).is_none(),
+            "mutation kind {} registered twice",
+            kind.name()
+        );
+
+        self.kinds.push(Arc::new(kind));
+    }
+
+    pub(crate) fn kinds(&self) -> &[Arc<dyn MutationKind>] {
+        &self.kinds
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Arc<dyn MutationKind>> {
+        self.kinds.iter().find(|kind| kind.name() == name)
+    }
+
+    /// Returns the kinds for which `is_enabled` returns true, in registration order.
+    pub(crate) fn enabled(&self, is_enabled: impl Fn(&str) -> bool) -> Vec<Arc<dyn MutationKind>> {
+        self.kinds
+            .iter()
+            .filter(|kind| is_enabled(kind.name()))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The names of the default kinds, in registration order.
+    const DEFAULT_KINDS: &[&str] = &[
+        "SwapLetters",
+        "RemovePunctuation",
+        "ReplaceHomophone",
+        "DeleteLetter",
+        "DuplicateLetter",
+        "KeyboardTypo",
+        "DuplicateWord",
+        "OmitWord",
+        "LowercaseSentenceStart",
+        "CapitalizeNoun",
+        "LowercaseProperNoun",
+        "SubstitutePunctuation",
+        "InsertPunctuation",
+        "DoubleSpace",
+        "RemoveSentenceSpace",
+        "JoinWords",
+        "SplitCompound",
+        "ApostropheError",
+        "ReplaceDeterminer",
+        "NumericError",
+        "ReplaceConfusable",
+    ];
+
+    fn registry() -> MutationRegistry {
+        MutationRegistry::with_defaults(
+            Arc::new(HomophoneSets::new_for_tests()),
+            Arc::new(ConfusableSets::new_for_tests()),
+        )
+    }
+
+    #[test]
+    fn test_registry_defaults() {
+        let registry = registry();
+        let names: Vec<&str> = registry.kinds().iter().map(|k| k.name()).collect();
+        assert_eq!(names, DEFAULT_KINDS);
+        assert!(registry.get("RemovePunctuation").is_some());
+        assert!(registry.get("Nonsense").is_none());
+    }
+
+    #[test]
+    fn test_registry_enabled() {
+        let registry = registry();
+        let enabled = registry.enabled(|name| name != "RemovePunctuation");
+        let names: Vec<&str> = enabled.iter().map(|k| k.name()).collect();
+        let expected: Vec<&str> = registry
+            .kinds()
+            .iter()
+            .map(|k| k.name())
+            .filter(|&name| name != "RemovePunctuation")
+            .collect();
+        assert_eq!(names, expected);
+        assert_eq!(names.len(), DEFAULT_KINDS.len() - 1);
+    }
+}
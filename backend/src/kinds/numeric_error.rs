@@ -0,0 +1,211 @@
+use std::sync::LazyLock;
+
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use regex::Regex;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::models::ProtectionOptions;
+use crate::span::Span;
+
+/// A number of two or more digits, possibly with thousands separators or a decimal point, such
+/// as "1,250.75". Punctuation ending the sentence isn't part of it.
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+(?:[.,]\d+)*").unwrap());
+
+/// Whether `number` has a thousands separator: a comma followed by exactly three digits.
+fn has_thousands_separator(number: &str) -> bool {
+    number.split(',').skip(1).any(|group| {
+        group
+            .split('.')
+            .next()
+            .is_some_and(|digits| digits.len() == 3)
+    })
+}
+
+/// Every way of getting `number` wrong, grouped by the kind of slip.
+fn variants(number: &str) -> Vec<Vec<String>> {
+    let bytes = number.as_bytes();
+    let digits: Vec<usize> = (0..bytes.len())
+        .filter(|&i| bytes[i].is_ascii_digit())
+        .collect();
+    let edit = |at: usize, remove: usize, insert: &str| {
+        format!("{}{insert}{}", &number[..at], &number[at + remove..])
+    };
+
+    let transposed = digits
+        .windows(2)
+        .filter(|w| w[1] == w[0] + 1 && bytes[w[0]] != bytes[w[1]])
+        .map(|w| {
+            edit(
+                w[0],
+                2,
+                &format!("{}{}", bytes[w[1]] as char, bytes[w[0]] as char),
+            )
+        })
+        .collect();
+
+    // A digit is only dropped from a run of two or more, so no separator is left dangling.
+    let dropped = digits
+        .iter()
+        .filter(|&&i| {
+            let before = i > 0 && bytes[i - 1].is_ascii_digit();
+            let after = bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+            before || after
+        })
+        .map(|&i| edit(i, 1, ""))
+        .collect();
+
+    let duplicated = digits.iter().map(|&i| edit(i, 0, &number[i..=i])).collect();
+
+    // The decimal point is the last separator, when it's a full stop.
+    let shifted = match number.rfind([',', '.']) {
+        Some(point) if bytes[point] == b'.' => {
+            let without = edit(point, 1, "");
+            [point - 1, point + 1]
+                .into_iter()
+                .filter(|&at| {
+                    let digit =
+                        |i: usize| without.as_bytes().get(i).is_some_and(u8::is_ascii_digit);
+                    at > 0 && digit(at - 1) && digit(at)
+                })
+                .map(|at| format!("{}.{}", &without[..at], &without[at..]))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    let swapped = if has_thousands_separator(number) {
+        let swap = |c: char| match c {
+            ',' => '.',
+            '.' => ',',
+            c => c,
+        };
+        vec![number.chars().map(swap).collect()]
+    } else {
+        Vec::new()
+    };
+
+    vec![transposed, dropped, duplicated, shifted, swapped]
+}
+
+/// Introduces an error into a number: transposes two adjacent digits, drops or duplicates a
+/// digit, shifts the decimal point, or swaps the thousands separators with the decimal point.
+///
+/// The span covers the whole number, so the error is highlighted in context.
+pub(crate) struct NumericError;
+
+impl NumericError {
+    pub(crate) const NAME: &'static str = "NumericError";
+}
+
+impl MutationKind for NumericError {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Transposes, drops or duplicates a digit, or misplaces a decimal point or separator."
+    }
+
+    /// Figures such as "$1,250", "20%" or "2024-01-31" are protected by default, but they're
+    /// exactly what this kind is for, so it's exempt from that protection.
+    fn protection(&self, options: ProtectionOptions) -> ProtectionOptions {
+        ProtectionOptions {
+            numbers: false,
+            times_and_dates: false,
+            ..options
+        }
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        // Numbers which are part of a word, such as "A4" or "3rd", are left alone.
+        NUMBER
+            .find_iter(text)
+            .filter(|m| {
+                let attached_before = text[..m.start()].ends_with(char::is_alphanumeric);
+                let attached_after = text[m.end()..].starts_with(char::is_alphanumeric);
+                !attached_before && !attached_after
+            })
+            .filter(|m| m.as_str().bytes().filter(u8::is_ascii_digit).count() >= 2)
+            .map(|m| Span::new(m.start(), m.end()))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let number = text.get(span.range())?;
+
+        if NUMBER.find(number).map(|m| m.as_str()) != Some(number) {
+            return None;
+        }
+
+        // Each kind of slip is equally likely, however many ways there are of making it.
+        let slips: Vec<Vec<String>> = variants(number)
+            .into_iter()
+            .map(|slip| slip.into_iter().filter(|v| v != number).collect())
+            .filter(|slip: &Vec<String>| !slip.is_empty())
+            .collect();
+        let replacement = slips.choose(rng)?.choose(rng)?.clone();
+
+        trace!("Replacing number '{number}' with '{replacement}'");
+
+        Some(replacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        NumericError
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("Sales of 1,250.75 rose by 12. Page 3, size A4, 3rd place, 45."),
+            vec!["1,250.75", "12", "45"]
+        );
+    }
+
+    #[test]
+    fn test_protection_lifts_only_numbers() {
+        let protection = NumericError.protection(ProtectionOptions::default());
+        assert!(!protection.numbers && !protection.times_and_dates);
+        assert!(protection.urls && protection.emails && protection.code);
+    }
+
+    #[test]
+    fn test_variants() {
+        let slips = variants("1,250.5");
+        assert_eq!(slips[0], vec!["1,520.5", "1,205.5"]);
+        assert_eq!(slips[1], vec!["1,50.5", "1,20.5", "1,25.5"]);
+        assert_eq!(slips[2][0], "11,250.5");
+        assert_eq!(slips[3], vec!["1,25.05"]);
+        assert_eq!(slips[4], vec!["1.250,5"]);
+
+        assert_eq!(variants("12.75")[3], vec!["1.275", "127.5"]);
+        assert!(variants("3,14")[4].is_empty());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..20 {
+            let replacement = NumericError
+                .apply("1,250.75", Span::new(0, 8), &mut rng)
+                .unwrap();
+            assert_ne!(replacement, "1,250.75");
+            assert!(replacement.starts_with(|c: char| c.is_ascii_digit()));
+        }
+
+        assert_eq!(NumericError.apply("12a", Span::new(0, 3), &mut rng), None);
+    }
+}
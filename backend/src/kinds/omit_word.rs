@@ -0,0 +1,172 @@
+use rand::rngs::StdRng;
+use std::sync::Arc;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::models::MutationRequestOptions;
+use crate::span::Span;
+
+/// Short words which are easy to leave out without noticing.
+const DEFAULT_FUNCTION_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on", "or",
+    "that", "the", "to", "was", "with",
+];
+
+/// Leaves out a short function word, as in "went the shop" for "went to the shop".
+///
+/// The word is removed along with the whitespace on one side of it, so the mutation's span in
+/// the mutated text is the empty gap where the word was.
+pub(crate) struct OmitWord {
+    /// The words which may be left out, in lowercase.
+    function_words: Vec<String>,
+}
+
+impl OmitWord {
+    pub(crate) const NAME: &'static str = "OmitWord";
+
+    pub(crate) fn new(function_words: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        OmitWord {
+            function_words: function_words
+                .into_iter()
+                .map(|word| word.as_ref().trim().to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Only lowercase words are left out, since a missing capitalised word leaves an obvious
+    /// gap at the start of a sentence.
+    fn is_function_word(&self, word: &str) -> bool {
+        !word.chars().any(char::is_uppercase) && self.function_words.iter().any(|w| w == word)
+    }
+}
+
+impl Default for OmitWord {
+    fn default() -> Self {
+        OmitWord::new(DEFAULT_FUNCTION_WORDS)
+    }
+}
+
+/// The length of the whitespace at the end of `text`, or `None` if it spans a line break.
+fn trailing_space(text: &str) -> Option<usize> {
+    let space = text.len() - text.trim_end().len();
+    (space > 0 && !text[text.len() - space..].contains('\n')).then_some(space)
+}
+
+/// The length of the whitespace at the start of `text`, or `None` if it spans a line break.
+fn leading_space(text: &str) -> Option<usize> {
+    let space = text.len() - text.trim_start().len();
+    (space > 0 && !text[..space].contains('\n')).then_some(space)
+}
+
+impl MutationKind for OmitWord {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Leaves out a short function word such as 'the' or 'to'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .into_iter()
+            .filter(|span| self.is_function_word(&text[span.range()]))
+            .filter_map(|span| {
+                // Take the space before the word where possible, so that a line break or
+                // punctuation after it stays where it was.
+                let after = &text[span.end..];
+
+                if let Some(space) = trailing_space(&text[..span.start]) {
+                    Some(Span::new(span.start - space, span.end))
+                } else if after.trim_start().starts_with(char::is_alphanumeric) {
+                    leading_space(after).map(|space| Span::new(span.start, span.end + space))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?.trim();
+
+        if !self.is_function_word(word) {
+            return None;
+        }
+
+        trace!("Omitting '{}' at byte {}", word, span.start);
+
+        Some(String::new())
+    }
+
+    fn configure(&self, options: &MutationRequestOptions) -> Option<Arc<dyn MutationKind>> {
+        let words = options.function_words.as_ref()?;
+        Some(Arc::new(OmitWord::new(words)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(omit: &OmitWord, text: &str) -> Vec<String> {
+        omit.find_candidates(text)
+            .into_iter()
+            .map(|span| text[span.range()].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates(
+                &OmitWord::default(),
+                "The cat went to\nthe shop, and it's for us."
+            ),
+            vec![" to", "the ", " and", " for"]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates_needs_surrounding_space() {
+        assert!(candidates(&OmitWord::default(), "to\nthe").is_empty());
+        assert!(candidates(&OmitWord::default(), "(a)").is_empty());
+    }
+
+    #[test]
+    fn test_custom_function_words() {
+        let omit = OmitWord::new(["Very", "so"]);
+        assert_eq!(
+            candidates(&omit, "It is so very hot."),
+            vec![" so", " very"]
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        assert!(
+            OmitWord::default()
+                .configure(&MutationRequestOptions::default())
+                .is_none()
+        );
+
+        let options = MutationRequestOptions {
+            function_words: Some(vec!["hot".to_string()]),
+            ..Default::default()
+        };
+        let omit = OmitWord::default().configure(&options).unwrap();
+        assert_eq!(omit.find_candidates("It is hot."), vec![Span::new(5, 9)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let omit = OmitWord::default();
+        assert_eq!(
+            omit.apply("go to the", Span::new(2, 5), &mut rng),
+            Some(String::new())
+        );
+        assert_eq!(omit.apply("go To the", Span::new(2, 5), &mut rng), None);
+    }
+}
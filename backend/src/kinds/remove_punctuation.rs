@@ -0,0 +1,70 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::span::Span;
+
+/// Removes a punctuation mark.
+pub(crate) struct RemovePunctuation;
+
+impl RemovePunctuation {
+    pub(crate) const NAME: &'static str = "RemovePunctuation";
+}
+
+impl MutationKind for RemovePunctuation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes a single punctuation mark."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        text.char_indices()
+            .filter(|(_, c)| c.is_ascii_punctuation())
+            .map(|(i, _)| Span::new(i, i + 1))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let target = text.get(span.range())?;
+
+        if target.len() != 1 || !target.chars().all(|c| c.is_ascii_punctuation()) {
+            return None;
+        }
+
+        trace!("Removing punctuation '{}' at byte {}", target, span.start);
+
+        Some(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_find_candidates() {
+        let candidates = RemovePunctuation.find_candidates("Oh, no!");
+        assert_eq!(candidates, vec![Span::new(2, 3), Span::new(6, 7)]);
+    }
+
+    #[test]
+    fn test_find_candidates_non_ascii() {
+        let candidates = RemovePunctuation.find_candidates("Olé, “no”!");
+        assert_eq!(candidates, vec![Span::new(4, 5), Span::new(14, 15)]);
+    }
+
+    #[test]
+    fn test_apply_rejects_non_punctuation() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let span = Span::new(0, 1);
+        assert_eq!(RemovePunctuation.apply("a.", span, &mut rng), None);
+        assert_eq!(
+            RemovePunctuation.apply(".a", span, &mut rng),
+            Some(String::new())
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::sentences::split_sentences;
+use crate::span::Span;
+
+/// Removes the space between two sentences, as in "It rained.We left."
+///
+/// The span covers the words on either side, so that the change can be highlighted.
+pub(crate) struct RemoveSentenceSpace;
+
+impl RemoveSentenceSpace {
+    pub(crate) const NAME: &'static str = "RemoveSentenceSpace";
+}
+
+/// Whether `gap` is the space between two sentences on the same line.
+fn is_sentence_space(gap: &str) -> bool {
+    !gap.is_empty() && gap.chars().all(|c| c.is_whitespace() && c != '\n')
+}
+
+impl MutationKind for RemoveSentenceSpace {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes the space after the end of a sentence."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        split_sentences(text)
+            .windows(2)
+            .filter(|pair| is_sentence_space(&text[pair[0].end..pair[1].start]))
+            .map(|pair| {
+                let start = text[..pair[0].end]
+                    .rfind(char::is_whitespace)
+                    .map_or(0, |i| i + 1);
+                let end = text[pair[1].start..]
+                    .find(char::is_whitespace)
+                    .map_or(text.len(), |i| pair[1].start + i);
+
+                Span::new(start, end)
+            })
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let source = text.get(span.range())?;
+
+        let gap_start = source.find(char::is_whitespace)?;
+        let gap_end = source[gap_start..]
+            .find(|c: char| !c.is_whitespace())
+            .map_or(source.len(), |i| gap_start + i);
+        let (before, gap, after) = (
+            &source[..gap_start],
+            &source[gap_start..gap_end],
+            &source[gap_end..],
+        );
+
+        let ends_sentence = before.ends_with(|c: char| !c.is_alphanumeric());
+        if !ends_sentence || !is_sentence_space(gap) || after.contains(char::is_whitespace) {
+            return None;
+        }
+
+        trace!("Removing the space between sentences in '{source}'");
+
+        Some(format!("{before}{after}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        RemoveSentenceSpace
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("It rained.  We left! Mr. Smith stayed.\n\nThe end."),
+            vec!["rained.  We", "left! Mr."]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            RemoveSentenceSpace.apply("Yes. No.", Span::new(0, 8), &mut rng),
+            Some("Yes.No.".to_string())
+        );
+        assert_eq!(
+            RemoveSentenceSpace.apply("Yes.\nNo.", Span::new(0, 8), &mut rng),
+            None
+        );
+        assert_eq!(
+            RemoveSentenceSpace.apply("Yes no.", Span::new(0, 7), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use rand::rngs::StdRng;
+use std::sync::Arc;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::confusables::ConfusableSets;
+use crate::span::Span;
+
+/// Replaces a word with one it's commonly confused with, such as "than" with "then".
+///
+/// Unlike [`ReplaceHomophone`](super::ReplaceHomophone), the words needn't sound the same.
+pub(crate) struct ReplaceConfusable {
+    confusables: Arc<ConfusableSets>,
+}
+
+impl ReplaceConfusable {
+    pub(crate) const NAME: &'static str = "ReplaceConfusable";
+
+    pub(crate) fn new(confusables: Arc<ConfusableSets>) -> Self {
+        ReplaceConfusable { confusables }
+    }
+}
+
+impl MutationKind for ReplaceConfusable {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces a word with one it's commonly confused with, such as 'then' with 'than'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .into_iter()
+            .filter(|span| {
+                self.confusables
+                    .find_matching_set(&text[span.range()])
+                    .is_some()
+            })
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+        let alternative = self.confusables.get_alternative(word, rng)?;
+
+        trace!("Replacing confusable '{}' with '{}'", word, alternative);
+
+        Some(alternative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn kind() -> ReplaceConfusable {
+        ReplaceConfusable::new(Arc::new(ConfusableSets::new_for_tests()))
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        let text = "Better late than never, then.";
+        let candidates = kind().find_candidates(text);
+        assert_eq!(candidates, vec![Span::new(12, 16), Span::new(24, 28)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            kind().apply("She led the way", Span::new(4, 7), &mut rng),
+            Some("lead".to_string())
+        );
+        assert_eq!(kind().apply("She", Span::new(0, 3), &mut rng), None);
+    }
+}
@@ -0,0 +1,221 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use tracing::trace;
+
+use super::{MutationKind, is_noun_like, sentence_first_words, words};
+use crate::case::match_case;
+use crate::span::Span;
+
+/// Endings of adjectives, adverbs and participles, such as "likely" or "interesting". A word
+/// with one of these after "less" or "that" is rarely a noun, so it's left alone.
+const MODIFIER_ENDINGS: &[&str] = &[
+    "able", "al", "ant", "ed", "ent", "er", "est", "ful", "ible", "ic", "ing", "ive", "ly", "ous",
+];
+
+/// Beginnings of words whose first letter doesn't tell whether they start with a vowel sound,
+/// such as the silent "h" of "hour" or the "you" sound of "university".
+const MISLEADING_BEGINNINGS: &[&str] = &[
+    "eu", "ewe", "heir", "herb", "honest", "honor", "honour", "hour", "once", "one", "ubiq", "uni",
+    "ura", "ure", "uri", "uro", "usa", "use", "usu", "ute", "uti",
+];
+
+/// What a determiner may be followed by for it to be replaced.
+#[derive(Clone, Copy)]
+enum Before {
+    /// Any word, as for "a" and "an".
+    Word,
+
+    /// A word whose first letter shows which of "a" or "an" it takes, as for "the".
+    PredictableWord,
+
+    /// A noun, as for "less" and "fewer".
+    Noun,
+
+    /// A singular noun, as for "this" and "that".
+    SingularNoun,
+
+    /// A plural noun, as for "these" and "those".
+    PluralNoun,
+}
+
+/// Determiners, what must follow them, and the ones they're commonly confused with. "the" is
+/// replaced by whichever of "a" or "an" doesn't suit the next word.
+const SUBSTITUTIONS: &[(&str, Before, &[&str])] = &[
+    ("a", Before::Word, &["an", "the"]),
+    ("an", Before::Word, &["a", "the"]),
+    ("fewer", Before::Noun, &["less"]),
+    ("less", Before::Noun, &["fewer"]),
+    ("that", Before::SingularNoun, &["those"]),
+    ("the", Before::PredictableWord, &[]),
+    ("these", Before::PluralNoun, &["this"]),
+    ("this", Before::SingularNoun, &["these"]),
+    ("those", Before::PluralNoun, &["that"]),
+];
+
+fn substitution(word: &str) -> Option<(Before, &'static [&'static str])> {
+    let word = word.to_lowercase();
+    SUBSTITUTIONS
+        .iter()
+        .find(|(determiner, _, _)| *determiner == word)
+        .map(|&(_, before, alternatives)| (before, alternatives))
+}
+
+/// Whether `next` may follow a determiner which needs `before` for it to be replaced.
+fn fits(before: Before, next: &str) -> bool {
+    let is_noun =
+        || is_noun_like(next) && !MODIFIER_ENDINGS.iter().any(|ending| next.ends_with(ending));
+
+    match before {
+        Before::Word => true,
+        Before::PredictableWord => {
+            let lowercase = next.to_lowercase();
+            let is_initialism = next.chars().count() > 1 && !next.chars().any(char::is_lowercase);
+
+            !is_initialism
+                && !MISLEADING_BEGINNINGS
+                    .iter()
+                    .any(|beginning| lowercase.starts_with(beginning))
+        }
+        Before::Noun => is_noun(),
+        Before::SingularNoun => is_noun() && !next.ends_with('s'),
+        Before::PluralNoun => is_noun() && next.ends_with('s'),
+    }
+}
+
+/// The indefinite article which doesn't suit `next`, judged by its first letter.
+fn wrong_indefinite_article(next: &str) -> &'static str {
+    if next.starts_with(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U']) {
+        "a"
+    } else {
+        "an"
+    }
+}
+
+/// Replaces a determiner with one it's commonly confused with, as in "a apple", "these book" or
+/// "less people".
+///
+/// "this", "that", "these", "those", "less" and "fewer" are only replaced before a word which
+/// looks like a noun, since they're just as often pronouns or adverbs, as in "so that we" or
+/// "less likely".
+pub(crate) struct ReplaceDeterminer;
+
+impl ReplaceDeterminer {
+    pub(crate) const NAME: &'static str = "ReplaceDeterminer";
+}
+
+impl MutationKind for ReplaceDeterminer {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces a determiner with a similar one, such as 'an' with 'a' or 'this' with 'these'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let first_words = sentence_first_words(text);
+
+        // Only a determiner followed by a word is replaced, and only a lowercase one unless it
+        // starts a sentence, to leave the likes of "Plan A" alone.
+        words(text)
+            .windows(2)
+            .filter(|pair| {
+                let (word, next) = (&text[pair[0].range()], &text[pair[1].range()]);
+
+                substitution(word).is_some_and(|(before, _)| fits(before, next))
+                    && text[pair[0].end..pair[1].start]
+                        .chars()
+                        .all(|c| c.is_whitespace() && c != '\n')
+                    && (word.chars().all(char::is_lowercase)
+                        || first_words.binary_search(&pair[0]).is_ok())
+            })
+            .map(|pair| pair[0])
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+        let (_, alternatives) = substitution(word)?;
+
+        let replacement = if alternatives.is_empty() {
+            // The next word may be hidden behind markup, in which case its sound is unknown.
+            let next = text[span.end..].trim_start();
+            if !next.starts_with(char::is_alphabetic) {
+                return None;
+            }
+            wrong_indefinite_article(next)
+        } else {
+            alternatives.choose(rng)?
+        };
+
+        trace!("Replacing determiner '{word}' with '{replacement}'");
+
+        Some(match_case(word, replacement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        ReplaceDeterminer
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("An apple and the pear. Plan A is less work than a\nthis. That."),
+            vec!["An", "the", "less"]
+        );
+        assert_eq!(
+            candidates("Read that book and those papers with fewer mistakes."),
+            vec!["that", "those", "fewer"]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates_skips_pronouns_and_adverbs() {
+        assert!(candidates("We left so that they could rest.").is_empty());
+        assert!(candidates("That said, this means those were less likely.").is_empty());
+        assert!(candidates("Buy this apples, those book and less interesting ones.").is_empty());
+    }
+
+    #[test]
+    fn test_find_candidates_skips_the_before_misleading_letters() {
+        // "an hour" and "a university" are correct, so they can't be the errors.
+        assert!(candidates("It took the hour to reach the university.").is_empty());
+        assert!(candidates("The honest one met the NHS and the European.").is_empty());
+        assert_eq!(candidates("the house and the unit"), vec!["the"]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut apply = |text: &str, end: usize| {
+            ReplaceDeterminer
+                .apply(text, Span::new(0, end), &mut rng)
+                .unwrap()
+        };
+
+        assert_eq!(apply("The egg", 3), "A");
+        assert_eq!(apply("the pear", 3), "an");
+        assert_eq!(apply("these books", 5), "this");
+        assert_eq!(apply("fewer people", 5), "less");
+
+        for _ in 0..10 {
+            assert!(["A", "The"].contains(&apply("An apple", 2).as_str()));
+            assert!(["an", "the"].contains(&apply("a egg", 1).as_str()));
+        }
+
+        assert_eq!(
+            ReplaceDeterminer.apply("the <b>apple</b>", Span::new(0, 3), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use rand::rngs::StdRng;
+use std::sync::Arc;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::homophones::HomophoneSets;
+use crate::span::Span;
+
+/// Replaces a word with one of its homophones.
+pub(crate) struct ReplaceHomophone {
+    homophones: Arc<HomophoneSets>,
+}
+
+impl ReplaceHomophone {
+    pub(crate) const NAME: &'static str = "ReplaceHomophone";
+
+    pub(crate) fn new(homophones: Arc<HomophoneSets>) -> Self {
+        ReplaceHomophone { homophones }
+    }
+}
+
+impl MutationKind for ReplaceHomophone {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces a word with a homophone, such as 'your' with 'you're'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .into_iter()
+            .filter(|span| {
+                self.homophones
+                    .find_matching_set(&text[span.range()])
+                    .is_some()
+            })
+            .inspect(|span| trace!("Found homophone candidate: '{}'", &text[span.range()]))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+        let alternative = self.homophones.get_alternative(word, rng)?;
+
+        trace!("Replacing homophone '{}' with '{}'", word, alternative);
+
+        Some(alternative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn kind() -> ReplaceHomophone {
+        ReplaceHomophone::new(Arc::new(HomophoneSets::new_for_tests()))
+    }
+
+    #[test]
+    fn test_find_candidates_excludes_surrounding_punctuation() {
+        let candidates = kind().find_candidates("('your' text, it's there.)");
+        assert_eq!(
+            candidates,
+            vec![Span::new(2, 6), Span::new(14, 18), Span::new(19, 24)]
+        );
+    }
+
+    #[test]
+    fn test_find_candidates_non_ascii() {
+        let candidates = kind().find_candidates("Café to go");
+        assert_eq!(candidates, vec![Span::new(6, 8)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            kind().apply("Was your car", Span::new(4, 8), &mut rng),
+            Some("you're".to_string())
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::{MutationKind, words};
+use crate::span::Span;
+
+/// Closed compounds which are commonly, and wrongly, written as two words, split into their
+/// parts.
+const COMPOUNDS: &[(&str, &str)] = &[
+    ("any", "body"),
+    ("any", "more"),
+    ("any", "one"),
+    ("any", "thing"),
+    ("any", "way"),
+    ("any", "where"),
+    ("can", "not"),
+    ("every", "body"),
+    ("every", "one"),
+    ("every", "thing"),
+    ("every", "where"),
+    ("her", "self"),
+    ("him", "self"),
+    ("in", "side"),
+    ("in", "to"),
+    ("it", "self"),
+    ("may", "be"),
+    ("my", "self"),
+    ("no", "body"),
+    ("no", "thing"),
+    ("no", "where"),
+    ("on", "to"),
+    ("out", "side"),
+    ("some", "body"),
+    ("some", "one"),
+    ("some", "thing"),
+    ("some", "times"),
+    ("some", "where"),
+    ("to", "day"),
+    ("to", "morrow"),
+    ("to", "night"),
+    ("up", "on"),
+    ("week", "end"),
+    ("with", "in"),
+    ("with", "out"),
+    ("your", "self"),
+];
+
+/// The byte offset at which `word` splits into the parts of a known compound. The compounds are
+/// all ASCII, so they're compared with `word` ignoring ASCII case only, and the offset always
+/// falls on a character boundary of `word` itself.
+fn split_point(word: &str) -> Option<usize> {
+    COMPOUNDS
+        .iter()
+        .find(|(first, second)| {
+            word.len() == first.len() + second.len()
+                && word
+                    .get(..first.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(first))
+                && word
+                    .get(first.len()..)
+                    .is_some_and(|end| end.eq_ignore_ascii_case(second))
+        })
+        .map(|(first, _)| first.len())
+}
+
+/// Splits a compound word in two, as in "any one" for "anyone".
+pub(crate) struct SplitCompound;
+
+impl SplitCompound {
+    pub(crate) const NAME: &'static str = "SplitCompound";
+}
+
+impl MutationKind for SplitCompound {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Splits a compound word in two, such as 'anyone' into 'any one'."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        words(text)
+            .into_iter()
+            .filter(|word| split_point(&text[word.range()]).is_some())
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let word = text.get(span.range())?;
+        let (first, second) = word.split_at_checked(split_point(word)?)?;
+
+        trace!("Splitting '{word}'");
+
+        Some(format!("{first} {second}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        SplitCompound
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("Anyone can't do it without cannot. Any one."),
+            vec!["Anyone", "without", "cannot"]
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_words_are_not_split() {
+        // The Kelvin sign lowercases to an ASCII "k", which changes the word's length in bytes.
+        let text = "A wee\u{212A}end away.";
+        assert!(candidates(text).is_empty());
+
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(SplitCompound.apply(text, Span::new(2, 11), &mut rng), None);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            SplitCompound.apply("Go Outside.", Span::new(3, 10), &mut rng),
+            Some("Out side".to_string())
+        );
+        assert_eq!(
+            SplitCompound.apply("Go outside.", Span::new(0, 2), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::span::Span;
+
+/// The marks each punctuation mark is commonly mistaken for, such as a comma for a semicolon, or
+/// a comma for a full stop, which splices two sentences together.
+const SUBSTITUTIONS: &[(char, &[char])] = &[
+    ('.', &[',', '?']),
+    (',', &[';', '.']),
+    (';', &[',', ':']),
+    (':', &[';']),
+    ('?', &['.']),
+    ('!', &['.', '?']),
+];
+
+fn substitutes(mark: char) -> Option<&'static [char]> {
+    SUBSTITUTIONS
+        .iter()
+        .find(|(m, _)| *m == mark)
+        .map(|(_, substitutes)| *substitutes)
+}
+
+/// Replaces a punctuation mark with one it's commonly confused with.
+pub(crate) struct SubstitutePunctuation;
+
+impl SubstitutePunctuation {
+    pub(crate) const NAME: &'static str = "SubstitutePunctuation";
+}
+
+impl MutationKind for SubstitutePunctuation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces a punctuation mark with a similar one, such as a semicolon with a comma."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+        // Only marks ending a word are candidates, which leaves out decimal points, ellipses and
+        // runs of marks like "?!".
+        (0..chars.len())
+            .filter(|&i| {
+                let (_, c) = chars[i];
+                let after_word = i > 0 && chars[i - 1].1.is_alphanumeric();
+                let before_space = chars
+                    .get(i + 1)
+                    .is_none_or(|(_, next)| next.is_whitespace());
+
+                substitutes(c).is_some() && after_word && before_space
+            })
+            .map(|i| Span::new(chars[i].0, chars[i].0 + 1))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, rng: &mut StdRng) -> Option<String> {
+        let mut chars = text.get(span.range())?.chars();
+
+        let (Some(mark), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+
+        // A comma can't end the passage.
+        let at_end = text[span.end..].trim().is_empty();
+        let options: Vec<char> = substitutes(mark)?
+            .iter()
+            .copied()
+            .filter(|&c| !(at_end && c == ','))
+            .collect();
+
+        let &substitute = options.choose(rng)?;
+
+        trace!(
+            "Replacing '{}' with '{}' at byte {}",
+            mark, substitute, span.start
+        );
+
+        Some(substitute.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn candidates(text: &str) -> Vec<&str> {
+        SubstitutePunctuation
+            .find_candidates(text)
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_candidates() {
+        assert_eq!(
+            candidates("Wait; really? Yes: 3.5 apples, done... Wow?! End."),
+            vec![";", "?", ":", ",", "."]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            let replacement = SubstitutePunctuation
+                .apply("It rained. We left.", Span::new(9, 10), &mut rng)
+                .unwrap();
+            assert!(replacement == "," || replacement == "?");
+        }
+
+        assert_eq!(
+            SubstitutePunctuation.apply("Why?", Span::new(3, 4), &mut rng),
+            Some(".".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_never_ends_passage_with_comma() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            assert_eq!(
+                SubstitutePunctuation.apply("The end. ", Span::new(7, 8), &mut rng),
+                Some("?".to_string())
+            );
+        }
+    }
+}
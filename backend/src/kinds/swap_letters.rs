@@ -0,0 +1,85 @@
+use rand::rngs::StdRng;
+use tracing::trace;
+
+use super::MutationKind;
+use crate::span::Span;
+
+/// Swaps a letter with the next letter.
+pub(crate) struct SwapLetters;
+
+impl SwapLetters {
+    pub(crate) const NAME: &'static str = "SwapLetters";
+}
+
+impl MutationKind for SwapLetters {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Swaps two adjacent, different letters."
+    }
+
+    fn find_candidates(&self, text: &str) -> Vec<Span> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+        chars
+            .windows(2)
+            .filter(|pair| {
+                let (a, b) = (pair[0].1, pair[1].1);
+
+                // Swapping identical letters wouldn't produce a visible error.
+                a.is_alphabetic() && b.is_alphabetic() && a != b
+            })
+            .map(|pair| Span::new(pair[0].0, pair[1].0 + pair[1].1.len_utf8()))
+            .collect()
+    }
+
+    fn apply(&self, text: &str, span: Span, _rng: &mut StdRng) -> Option<String> {
+        let mut chars = text.get(span.range())?.chars();
+
+        let (Some(first), Some(second), None) = (chars.next(), chars.next(), chars.next()) else {
+            return None;
+        };
+
+        trace!(
+            "Swapping letters at byte {}: '{}' and '{}'",
+            span.start, first, second
+        );
+
+        Some([second, first].iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_find_candidates() {
+        let candidates = SwapLetters.find_candidates("ab c'd");
+        assert_eq!(candidates, vec![Span::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_find_candidates_skips_identical_letters() {
+        let candidates = SwapLetters.find_candidates("all");
+        assert_eq!(candidates, vec![Span::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_find_candidates_non_ascii() {
+        let candidates = SwapLetters.find_candidates("né");
+        assert_eq!(candidates, vec![Span::new(0, 3)]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(
+            SwapLetters.apply("héllo", Span::new(1, 4), &mut rng),
+            Some("lé".to_string())
+        );
+    }
+}
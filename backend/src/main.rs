@@ -1,8 +1,20 @@
+mod case;
+mod conflicts;
+mod confusables;
+mod edits;
 mod env;
 mod handler;
 mod homophones;
+mod html;
+mod kinds;
+mod markdown;
 mod models;
 mod mutator;
+mod presets;
+mod protected;
+mod sentences;
+mod span;
+mod word_sets;
 
 use std::fs::File;
 use std::io::BufReader;
@@ -16,15 +28,17 @@ use axum::{
     response::Response,
     routing::{get, post},
 };
+use confusables::ConfusableSets;
 use env::EnvironmentVariables;
 use handler::AppState;
 use homophones::HomophoneSets;
+use kinds::MutationRegistry;
 use tokio::signal;
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
-use tracing::{Level, Span, info};
+use tracing::{Level, Span};
 use tracing_appender::rolling;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
@@ -38,12 +52,15 @@ async fn main() -> anyhow::Result<()> {
 
     setup_logging();
 
-    // Load homophones
-    let file = File::open("homophones.json").expect("Failed to open homophones.json");
-    let reader = BufReader::new(file);
-    let sets: Vec<Vec<String>> = serde_json::from_reader(reader).expect("Failed to parse homophones.json");
-    let homophones = Arc::new(HomophoneSets { sets });
-    let state = AppState { homophones };
+    // Load homophones and commonly confused words
+    let homophones = Arc::new(HomophoneSets {
+        sets: load_word_sets("homophones.json"),
+    });
+    let confusables = Arc::new(ConfusableSets {
+        sets: load_word_sets("confusables.json"),
+    });
+    let registry = Arc::new(MutationRegistry::with_defaults(homophones, confusables));
+    let state = AppState { registry };
 
     let app = app(&env, state);
 
@@ -63,6 +80,13 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Loads a JSON list of word sets, such as the homophones, from the given file.
+fn load_word_sets(path: &str) -> Vec<Vec<String>> {
+    let file = File::open(path).unwrap_or_else(|e| panic!("Failed to open {path}: {e}"));
+    let reader = BufReader::new(file);
+    serde_json::from_reader(reader).unwrap_or_else(|e| panic!("Failed to parse {path}: {e}"))
+}
+
 fn setup_logging() {
     let file_appender = rolling::daily("logs", "application.log");
     let (non_blocking_appender, _guard) = tracing_appender::non_blocking(file_appender);
@@ -112,15 +136,16 @@ fn app(env: &EnvironmentVariables, state: AppState) -> Router {
             );
         });
 
-    let app = Router::new()
+    Router::new()
         .route(get_route("health").as_str(), get(handler::health))
         .route(get_route("mutate").as_str(), post(handler::mutate))
+        .route(get_route("mutations").as_str(), get(handler::mutations))
+        .route(get_route("presets").as_str(), get(handler::presets))
+        .route(get_route("restore").as_str(), post(handler::restore))
         .fallback(handler::fallback)
         .layer(cors)
         .layer(tracer)
-        .with_state(state);
-
-    app
+        .with_state(state)
 }
 
 fn get_route<S: AsRef<str>>(endpoint: S) -> String {
@@ -158,11 +183,13 @@ async fn shutdown_signal() {
 mod tests {
     use crate::{
         app,
+        confusables::ConfusableSets,
         env::EnvironmentVariables,
         get_route, handler,
-        models::{MutationRequest, MutationRequestOptions},
-        homophones::HomophoneSets,
         handler::AppState,
+        homophones::HomophoneSets,
+        kinds::MutationRegistry,
+        models::{MutationRequest, MutationRequestOptions},
     };
     use axum::{
         Router,
@@ -171,12 +198,15 @@ mod tests {
     };
     use http_body_util::BodyExt;
     use serde_json::json;
-    use tower::ServiceExt;
     use std::sync::Arc;
+    use tower::ServiceExt;
 
     fn get_test_state() -> AppState {
         AppState {
-            homophones: Arc::new(HomophoneSets::new_for_tests()),
+            registry: Arc::new(MutationRegistry::with_defaults(
+                Arc::new(HomophoneSets::new_for_tests()),
+                Arc::new(ConfusableSets::new_for_tests()),
+            )),
         }
     }
 
@@ -235,6 +265,810 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn mutations_lists_registered_kinds() {
+        let state = get_test_state();
+        let expected: Vec<&str> = state.registry.kinds().iter().map(|k| k.name()).collect();
+
+        let response = app(&EnvironmentVariables::empty(), state.clone())
+            .oneshot(
+                Request::builder()
+                    .uri(get_route("mutations"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let kinds: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let names: Vec<&str> = kinds
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| k["name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(names, expected);
+    }
+
+    #[tokio::test]
+    async fn mutate_honours_per_kind_options() {
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+        let req = json!({
+            "text": "Your car, your rules.",
+            "config": {
+                "mutationRate": 1.0,
+                "allowSwaps": true,
+                "strategy": "Candidates",
+                "mutations": {
+                    "SwapLetters": { "enabled": false },
+                    "ReplaceHomophone": { "enabled": true }
+                }
+            }
+        });
+
+        let body = send_raw_json_request(app, &req).await;
+        assert_eq!(body["mutated_text"], "You're car, you're rules.");
+        assert!(
+            body["mutations"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|m| m["type"] == "ReplaceHomophone")
+        );
+    }
+
+    #[tokio::test]
+    async fn mutate_reports_offsets_in_requested_unit() {
+        // "😀" is one char, four UTF-8 bytes and two UTF-16 code units.
+        let text = "😀 your car";
+
+        for (unit, expected) in [("Chars", 2), ("Utf8", 5), ("Utf16", 3)] {
+            let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+            let req = json!({
+                "text": text,
+                "config": {
+                    "mutationRate": 1.0,
+                    "allowHomophones": true,
+                    "offsetUnit": unit
+                }
+            });
+
+            let body = send_raw_json_request(app, &req).await;
+
+            let mutation = &body["mutations"][0];
+            assert_eq!(mutation["original_start"], expected);
+            assert_eq!(mutation["original_end"], expected + 4);
+            assert_eq!(mutation["start"], expected);
+            assert_eq!(mutation["end"], expected + 6);
+        }
+    }
+
+    #[tokio::test]
+    async fn mutate_rejects_negative_weights() {
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+        let req = json!({
+            "text": "Your car",
+            "config": {
+                "mutationRate": 1.0,
+                "mutations": { "SwapLetters": { "enabled": true, "weight": -1.0 } }
+            }
+        });
+
+        let response = send_json_request(app, serde_json::from_value(req).unwrap()).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn mutate_applies_exact_count() {
+        let req = json!({
+            "text": "The cat sat. The dog ran. Your bird flew.",
+            "config": { "count": 5, "allowSwaps": true, "seed": 42 }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(body["mutations"].as_array().unwrap().len(), 5);
+        assert!(body.get("shortfall").is_none());
+    }
+
+    #[tokio::test]
+    async fn mutate_reports_shortfall() {
+        let req = json!({
+            "text": "Your car.",
+            "config": {
+                "count": 3,
+                "mutations": { "ReplaceHomophone": { "enabled": true, "min": 2 } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(
+            body["shortfall"],
+            json!({
+                "requested": 3,
+                "applied": 1,
+                "kinds": [{ "type": "ReplaceHomophone", "minimum": 2, "applied": 1 }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn mutate_rejects_min_above_max() {
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+        let req = json!({
+            "text": "Your car",
+            "config": {
+                "mutationRate": 1.0,
+                "mutations": { "SwapLetters": { "enabled": true, "min": 3, "max": 2 } }
+            }
+        });
+
+        let response = send_json_request(app, serde_json::from_value(req).unwrap()).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn presets_lists_presets() {
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(get_route("presets"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let presets: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let names: Vec<&str> = presets
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(names, vec!["easy", "standard", "expert"]);
+        assert_eq!(presets[1]["mutations"]["SwapLetters"]["weight"], json!(1.0));
+        assert_eq!(presets[0]["strategy"], "Paragraphs");
+        assert_eq!(presets[0]["spacing"]["minWords"], 5);
+        assert_eq!(presets[2]["spacing"]["minWords"], 0);
+    }
+
+    #[tokio::test]
+    async fn mutate_applies_preset() {
+        let req = json!({
+            "text": "The cat sat. The dog ran. Your bird flew.",
+            "config": { "preset": "expert", "seed": 42 }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert!(!body["mutations"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn mutate_prefers_explicit_settings_over_preset() {
+        // Disable everything in the preset but swaps and homophones explicitly...
+        let state = get_test_state();
+        let disabled: serde_json::Map<String, serde_json::Value> = state
+            .registry
+            .kinds()
+            .iter()
+            .map(|kind| kind.name())
+            .filter(|&name| name != "SwapLetters" && name != "ReplaceHomophone")
+            .map(|name| (name.to_string(), json!({ "enabled": false })))
+            .collect();
+
+        // ...and swaps through the legacy flag, and lift the preset's spacing.
+        let req = json!({
+            "text": "Your car, your rules. Were you there?",
+            "config": {
+                "preset": "standard",
+                "count": 3,
+                "allowSwaps": false,
+                "spacing": { "minWords": 0 },
+                "mutations": disabled
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), state);
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 3);
+        assert!(mutations.iter().all(|m| m["type"] == "ReplaceHomophone"));
+    }
+
+    #[tokio::test]
+    async fn mutate_applies_preset_spacing_unless_overridden() {
+        let state = get_test_state();
+        let only_homophones: serde_json::Map<String, serde_json::Value> = state
+            .registry
+            .kinds()
+            .iter()
+            .map(|kind| kind.name())
+            .filter(|&name| name != "ReplaceHomophone")
+            .map(|name| (name.to_string(), json!({ "enabled": false })))
+            .collect();
+
+        let mut req = json!({
+            "text": "Your car, your rules.",
+            "config": { "preset": "standard", "count": 2, "mutations": only_homophones }
+        });
+
+        // "standard" keeps two words between mutations, so only one "your" can change...
+        let body = send_raw_json_request(app(&EnvironmentVariables::empty(), state), &req).await;
+        assert_eq!(body["mutations"].as_array().unwrap().len(), 1);
+
+        // ...unless the request sets its own spacing.
+        req["config"]["spacing"] = json!({ "minWords": 0 });
+        let body =
+            send_raw_json_request(app(&EnvironmentVariables::empty(), get_test_state()), &req)
+                .await;
+        assert_eq!(body["mutations"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn mutate_rejects_unknown_preset() {
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+
+        let req = json!({
+            "text": "Your car",
+            "config": { "preset": "impossible" }
+        });
+
+        let response = send_json_request(app, serde_json::from_value(req).unwrap()).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn restore_undoes_mutations() {
+        let req = json!({
+            "mutated_text": "😀 you're car",
+            "offsetUnit": "Utf16",
+            "mutations": [{
+                "start": 3,
+                "end": 9,
+                "original": "your",
+                "replacement": "you're",
+                "type": "ReplaceHomophone"
+            }]
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let response = post_json(app, "restore", &req).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["text"], "😀 your car");
+    }
+
+    #[tokio::test]
+    async fn restore_rejects_mismatched_replacement() {
+        let req = json!({
+            "mutated_text": "😀 your car",
+            "offsetUnit": "Utf16",
+            "mutations": [{
+                "start": 3,
+                "end": 7,
+                "original": "you're",
+                "replacement": "they're",
+                "type": "ReplaceHomophone"
+            }]
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let response = post_json(app, "restore", &req).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // The offset is reported in the request's unit rather than in bytes.
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            "The mutated text doesn't contain \"they're\" at offset 3"
+        );
+    }
+
+    #[tokio::test]
+    async fn mutate_reports_original_and_replacement_text() {
+        let text = "Café, your car's 😀 over there!";
+        let req = json!({
+            "text": text,
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "allowSwaps": true,
+                "allowPunctuationRemoval": true,
+                "allowHomophones": true,
+                "seed": 42
+            }
+        });
+
+        let body =
+            send_raw_json_request(app(&EnvironmentVariables::empty(), get_test_state()), &req)
+                .await;
+
+        let chars: Vec<char> = text.chars().collect();
+        let mutated: Vec<char> = body["mutated_text"].as_str().unwrap().chars().collect();
+        let slice = |chars: &[char], start: &serde_json::Value, end: &serde_json::Value| {
+            let offset =
+                |value: &serde_json::Value| usize::try_from(value.as_u64().unwrap()).unwrap();
+            let range = offset(start)..offset(end);
+            chars[range].iter().collect::<String>()
+        };
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert!(!mutations.is_empty());
+
+        for m in mutations {
+            assert_eq!(
+                m["original"],
+                slice(&chars, &m["original_start"], &m["original_end"])
+            );
+            assert_eq!(m["replacement"], slice(&mutated, &m["start"], &m["end"]));
+        }
+
+        // The answer key round-trips through the restore endpoint unchanged.
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let response = post_json(app, "restore", &body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let restored = response.into_body().collect().await.unwrap().to_bytes();
+        let restored: serde_json::Value = serde_json::from_slice(&restored).unwrap();
+        assert_eq!(restored["text"], text);
+    }
+
+    #[tokio::test]
+    async fn mutate_honours_protection_options() {
+        let text = "Visit www.example.com.";
+        let config = |protect_urls: bool| {
+            json!({
+                "text": text,
+                "config": {
+                    "mutationRate": 1.0,
+                    "strategy": "Candidates",
+                    "allowPunctuationRemoval": true,
+                    "protect": { "urls": protect_urls }
+                }
+            })
+        };
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app.clone(), &config(true)).await;
+        assert_eq!(body["mutated_text"], "Visit www.example.com");
+
+        let body = send_raw_json_request(app, &config(false)).await;
+        assert_eq!(body["mutated_text"], "Visit wwwexamplecom");
+    }
+
+    #[tokio::test]
+    async fn mutate_preserves_markdown_syntax() {
+        let text = "## Heading\n\n* `a.b` and [link](http://x.y/z)";
+        let req = json!({
+            "text": text,
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "allowPunctuationRemoval": true,
+                "format": "Markdown"
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        // The only punctuation is Markdown syntax, so nothing can be mutated.
+        assert_eq!(body["mutated_text"], text);
+        assert!(body["mutations"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn mutate_marks_html_mutations() {
+        let text = "<p class=\"a, b\">Hello, world</p><!-- c, d --><script>e, f</script>";
+        let req = json!({
+            "text": text,
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "allowSwaps": false,
+                "allowPunctuationRemoval": true,
+                "allowHomophones": false,
+                "format": "Html",
+                "markMutations": true
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(
+            body["mutated_text"],
+            "<p class=\"a, b\">Hello<mark data-type=\"RemovePunctuation\"></mark> world</p>\
+             <!-- c, d --><script>e, f</script>"
+        );
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 1);
+        assert_eq!(mutations[0]["original_start"], 21);
+        assert_eq!(mutations[0]["original"], ",");
+    }
+
+    #[tokio::test]
+    async fn mutate_spreads_mutations_across_paragraphs() {
+        let text = "One, two, three, four.\n\nFive, six, seven, eight.";
+        let req = json!({
+            "text": text,
+            "config": {
+                "count": 4,
+                "strategy": "Paragraphs",
+                "allowSwaps": false,
+                "allowPunctuationRemoval": true,
+                "allowHomophones": false,
+                "spacing": { "minWords": 1 }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        // Every other punctuation mark in each paragraph.
+        let starts: Vec<u64> = body["mutations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["original_start"].as_u64().unwrap())
+            .collect();
+        assert_eq!(starts.len(), 4);
+        assert_eq!(starts.iter().filter(|&&start| start < 22).count(), 2);
+    }
+
+    #[tokio::test]
+    async fn mutate_deletes_and_duplicates_letters() {
+        let req = json!({
+            "text": "The committee occurred to meet until noon.",
+            "config": {
+                "count": 4,
+                "strategy": "Candidates",
+                "mutations": {
+                    "DeleteLetter": { "enabled": true, "min": 2 },
+                    "DuplicateLetter": { "enabled": true, "min": 2 }
+                }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 4);
+        for m in mutations {
+            match m["type"].as_str().unwrap() {
+                "DeleteLetter" => assert_eq!(m["replacement"], ""),
+                "DuplicateLetter" => {
+                    let original = m["original"].as_str().unwrap();
+                    assert_eq!(m["replacement"], original.repeat(2));
+                }
+                other => panic!("unexpected type {other}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn mutate_uses_keyboard_layout() {
+        let req = json!({
+            "text": "mmm",
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "keyboardLayout": "Azerty",
+                "mutations": { "KeyboardTypo": { "enabled": true } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        // On AZERTY, "m" sits on the home row between "l" and "ù", below "p".
+        let mutations = body["mutations"].as_array().unwrap();
+        assert!(!mutations.is_empty());
+        for m in mutations {
+            assert_eq!(m["type"], "KeyboardTypo");
+            assert!(["l", "p", "ù"].contains(&m["replacement"].as_str().unwrap()));
+        }
+    }
+
+    #[tokio::test]
+    async fn mutate_omits_configured_words() {
+        let text = "It is so very hot.";
+        let req = json!({
+            "text": text,
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "functionWords": ["very"],
+                "mutations": { "OmitWord": { "enabled": true } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(body["mutated_text"], "It is so hot.");
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 1);
+        assert_eq!(mutations[0]["original"], " very");
+        assert_eq!(mutations[0]["start"], mutations[0]["end"]);
+    }
+
+    #[tokio::test]
+    async fn mutate_introduces_capitalization_errors() {
+        let req = json!({
+            "text": "Then I met Anna in the park.",
+            "config": {
+                "count": 3,
+                "strategy": "Candidates",
+                "mutations": {
+                    "LowercaseSentenceStart": { "enabled": true, "min": 1 },
+                    "CapitalizeNoun": { "enabled": true, "min": 1 },
+                    "LowercaseProperNoun": { "enabled": true, "min": 1 }
+                }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 3);
+        assert_eq!(mutations[0]["type"], "LowercaseSentenceStart");
+        assert_eq!(mutations[0]["replacement"], "then");
+        assert_eq!(mutations[1]["type"], "LowercaseProperNoun");
+        assert!(mutations[1]["replacement"] == "i" || mutations[1]["replacement"] == "anna");
+        assert_eq!(mutations[2]["type"], "CapitalizeNoun");
+        assert_eq!(mutations[2]["replacement"], "Park");
+    }
+
+    #[tokio::test]
+    async fn mutate_substitutes_and_inserts_punctuation() {
+        let req = json!({
+            "text": "The dog barked; we ran.",
+            "config": {
+                "count": 2,
+                "strategy": "Candidates",
+                "mutations": {
+                    "SubstitutePunctuation": { "enabled": true, "min": 1 },
+                    "InsertPunctuation": { "enabled": true, "min": 1 }
+                }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 2);
+        for m in mutations {
+            match m["type"].as_str().unwrap() {
+                "SubstitutePunctuation" => {
+                    assert!(m["original"] == ";" || m["original"] == ".");
+                    assert_ne!(m["replacement"], m["original"]);
+                }
+                "InsertPunctuation" => {
+                    assert_eq!(m["original"], "");
+                    assert_eq!(m["replacement"], ",");
+                }
+                other => panic!("unexpected type {other}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn mutate_introduces_whitespace_errors() {
+        let req = json!({
+            "text": "It rained. We saw a lot. Anyone could.",
+            "config": {
+                "mutationRate": 1.0,
+                "strategy": "Candidates",
+                "allowSwaps": false,
+                "allowPunctuationRemoval": false,
+                "allowHomophones": false,
+                "mutations": {
+                    "DoubleSpace": { "enabled": true },
+                    "RemoveSentenceSpace": { "enabled": true },
+                    "JoinWords": { "enabled": true },
+                    "SplitCompound": { "enabled": true }
+                }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert!(!mutations.is_empty());
+        for m in mutations {
+            let original = m["original"].as_str().unwrap();
+            let replacement = m["replacement"].as_str().unwrap();
+            let without_spaces = |s: &str| s.replace(' ', "");
+
+            assert_ne!(original, replacement);
+            assert_eq!(without_spaces(original), without_spaces(replacement));
+            assert!(m["end"].as_u64() > m["start"].as_u64());
+        }
+    }
+
+    #[tokio::test]
+    async fn mutate_misuses_apostrophes() {
+        let req = json!({
+            "text": "The dog's bone lay under two apples.",
+            "config": {
+                "count": 2,
+                "strategy": "Candidates",
+                "mutations": { "ApostropheError": { "enabled": true, "min": 2 } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 2);
+
+        assert_eq!(mutations[0]["type"], "ApostropheError");
+        assert_eq!(mutations[0]["original"], "dog's");
+        assert!(mutations[0]["replacement"] == "dogs" || mutations[0]["replacement"] == "dogs'");
+
+        assert_eq!(mutations[1]["type"], "ApostropheError");
+        assert_eq!(mutations[1]["original"], "apples");
+        assert_eq!(mutations[1]["replacement"], "apple's");
+    }
+
+    #[tokio::test]
+    async fn mutate_replaces_determiners() {
+        let req = json!({
+            "text": "The owl ate these books so that it could rest.",
+            "config": {
+                "count": 2,
+                "strategy": "Candidates",
+                "mutations": { "ReplaceDeterminer": { "enabled": true, "min": 2 } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(
+            body["mutated_text"],
+            "A owl ate this books so that it could rest."
+        );
+    }
+
+    #[tokio::test]
+    async fn mutate_introduces_numeric_errors_into_protected_figures() {
+        // Currency and percentages are protected by default, but not from NumericError.
+        let req = json!({
+            "text": "Costs rose 20% to $1,250 this year.",
+            "config": {
+                "count": 2,
+                "allowSwaps": false,
+                "allowPunctuationRemoval": false,
+                "allowHomophones": false,
+                "mutations": { "NumericError": { "enabled": true, "min": 2 } }
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        let originals: Vec<&str> = mutations
+            .iter()
+            .map(|m| m["original"].as_str().unwrap())
+            .collect();
+        assert_eq!(originals, vec!["20", "1,250"]);
+        assert!(mutations.iter().all(|m| m["replacement"] != m["original"]));
+    }
+
+    #[tokio::test]
+    async fn mutate_introduces_numeric_errors_only_when_enabled() {
+        let request = |mutations: serde_json::Value| {
+            json!({
+                "text": "Revenue was 12345 this year.",
+                "config": {
+                    "count": 1,
+                    "allowSwaps": false,
+                    "allowPunctuationRemoval": false,
+                    "allowHomophones": false,
+                    "mutations": mutations
+                }
+            })
+        };
+
+        let body = send_raw_json_request(
+            app(&EnvironmentVariables::empty(), get_test_state()),
+            &request(json!({})),
+        )
+        .await;
+        assert!(body["mutations"].as_array().unwrap().is_empty());
+
+        let body = send_raw_json_request(
+            app(&EnvironmentVariables::empty(), get_test_state()),
+            &request(json!({ "NumericError": { "enabled": true } })),
+        )
+        .await;
+
+        let mutations = body["mutations"].as_array().unwrap();
+        assert_eq!(mutations.len(), 1);
+        assert_eq!(mutations[0]["type"], "NumericError");
+        assert_eq!(mutations[0]["original"], "12345");
+        assert_ne!(mutations[0]["replacement"], "12345");
+    }
+
+    #[tokio::test]
+    async fn mutate_replaces_confusables_when_allowed() {
+        let req = json!({
+            "text": "Better late than never.",
+            "config": {
+                "count": 1,
+                "allowSwaps": false,
+                "allowPunctuationRemoval": false,
+                "allowHomophones": false,
+                "allowConfusables": true
+            }
+        });
+
+        let app = app(&EnvironmentVariables::empty(), get_test_state());
+        let body = send_raw_json_request(app, &req).await;
+
+        assert_eq!(body["mutated_text"], "Better late then never.");
+        assert_eq!(body["mutations"][0]["type"], "ReplaceConfusable");
+    }
+
+    async fn post_json(app: Router, endpoint: &str, req: &serde_json::Value) -> Response<Body> {
+        app.oneshot(
+            Request::builder()
+                .method(http::Method::POST)
+                .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                .uri(get_route(endpoint))
+                .body(Body::from(serde_json::to_vec(req).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn send_raw_json_request(app: Router, req: &serde_json::Value) -> serde_json::Value {
+        let response = post_json(app, "mutate", req).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
     async fn send_json_request(app: Router, req: MutationRequest) -> Response<Body> {
         app.oneshot(
             Request::builder()
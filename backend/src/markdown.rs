@@ -0,0 +1,111 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::span::Span;
+
+/// Finds the parts of a Markdown document which aren't prose, so they can be protected from
+/// mutation: syntax such as `#`, `*` and link destinations, code, HTML and image alt text.
+///
+/// Prose is only recognised where the source reads exactly as the rendered text, so escapes
+/// and entities such as `\*` and `&amp;` are left alone too.
+pub(crate) fn syntax_regions(source: &str) -> Vec<Span> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut regions = Vec::new();
+    let mut position = 0;
+    let mut code_depth = 0usize;
+
+    for (event, range) in Parser::new_ext(source, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::Image { .. }) => code_depth += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::Image) => code_depth -= 1,
+            Event::Text(text) if code_depth == 0 && source[range.clone()] == *text => {
+                // The character after a backslash is escaped syntax rather than prose.
+                let mut start = range.start;
+                if source[..start].ends_with('\\') {
+                    start += text.chars().next().map_or(0, char::len_utf8);
+                }
+
+                if start > position {
+                    regions.push(Span::new(position, start));
+                }
+                position = position.max(range.end);
+            }
+            _ => {}
+        }
+    }
+
+    if source.len() > position {
+        regions.push(Span::new(position, source.len()));
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The prose left over once the syntax regions are removed.
+    fn prose(source: &str) -> Vec<&str> {
+        let mut prose = Vec::new();
+        let mut position = 0;
+
+        for region in syntax_regions(source) {
+            if region.start > position {
+                prose.push(&source[position..region.start]);
+            }
+            position = region.end;
+        }
+
+        if source.len() > position {
+            prose.push(&source[position..]);
+        }
+
+        prose
+    }
+
+    #[test]
+    fn test_plain_paragraph_is_all_prose() {
+        assert_eq!(prose("Just some text."), vec!["Just some text."]);
+        assert!(syntax_regions("Just some text.").is_empty());
+    }
+
+    #[test]
+    fn test_headings_and_emphasis() {
+        assert_eq!(
+            prose("# Your title\n\nSome *very* **bold** text."),
+            vec!["Your title", "Some ", "very", " ", "bold", " text."]
+        );
+    }
+
+    #[test]
+    fn test_links_keep_only_their_text() {
+        assert_eq!(
+            prose("See [their site](https://example.com/a_b) now."),
+            vec!["See ", "their site", " now."]
+        );
+    }
+
+    #[test]
+    fn test_code_is_never_prose() {
+        let source = "Run `cargo test`.\n\n```rust\nlet your = 1;\n```\n\nDone.";
+        assert_eq!(prose(source), vec!["Run ", ".", "Done."]);
+    }
+
+    #[test]
+    fn test_escapes_and_images_are_not_prose() {
+        assert_eq!(
+            prose("A \\* star ![your alt](x.png) &amp; more"),
+            vec!["A ", " star ", " ", " more"]
+        );
+    }
+
+    #[test]
+    fn test_lists_and_tables() {
+        assert_eq!(
+            prose("- one\n- two\n\n| a | b |\n|---|---|\n| c | d |"),
+            vec!["one", "two", "a", "b", "c", "d"]
+        );
+    }
+}
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+
+use crate::kinds::{RemovePunctuation, ReplaceConfusable, ReplaceHomophone, SwapLetters};
+use crate::presets::Preset;
+use crate::span::Span;
+
 // Contracts
 
 // Requests
@@ -14,20 +20,312 @@ pub struct MutationRequest {
 
 #[derive(serde::Serialize, serde::Deserialize, Default)]
 pub struct MutationRequestOptions {
-    #[serde(rename = "mutationRate")]
-    pub mutation_rate: f32,
+    /// The name of a preset supplying defaults for the rate and the enabled kinds and their
+    /// weights. Any of those given explicitly in the request take precedence.
+    #[serde(rename = "preset", default)]
+    pub preset: Option<String>,
 
-    #[serde(rename = "allowSwaps")]
-    pub allow_swaps: bool,
+    #[serde(rename = "mutationRate", default)]
+    pub mutation_rate: Option<f32>,
 
-    #[serde(rename = "allowPunctuationRemoval")]
-    pub allow_punctuation_removal: bool,
+    /// The exact number of mutations to apply. When given, `mutation_rate` is ignored.
+    #[serde(rename = "count", default)]
+    pub count: Option<usize>,
 
-    #[serde(rename = "allowHomophones")]
-    pub allow_homophones: bool,
+    #[serde(rename = "allowSwaps", default)]
+    pub allow_swaps: Option<bool>,
+
+    #[serde(rename = "allowPunctuationRemoval", default)]
+    pub allow_punctuation_removal: Option<bool>,
+
+    #[serde(rename = "allowHomophones", default)]
+    pub allow_homophones: Option<bool>,
+
+    /// Whether to replace words with ones they're commonly confused with, as opposed to strict
+    /// homophones.
+    #[serde(rename = "allowConfusables", default)]
+    pub allow_confusables: Option<bool>,
 
     #[serde(rename = "seed")]
     pub seed: Option<u64>,
+
+    #[serde(rename = "strategy", default)]
+    pub strategy: Option<MutationStrategy>,
+
+    /// The unit used for the offsets in the response.
+    #[serde(rename = "offsetUnit", default)]
+    pub offset_unit: OffsetUnit,
+
+    /// The format of the passage, which determines what counts as mutable prose.
+    #[serde(rename = "format", default)]
+    pub format: InputFormat,
+
+    /// Whether to wrap each mutation in a `<mark data-type="...">` element, producing an answer
+    /// key. Only applies to HTML passages.
+    #[serde(rename = "markMutations", default)]
+    pub mark_mutations: bool,
+
+    /// The keyboard layout whose neighbouring keys `KeyboardTypo` substitutes.
+    #[serde(rename = "keyboardLayout", default)]
+    pub keyboard_layout: KeyboardLayout,
+
+    /// The words `OmitWord` may leave out, in place of its default list of short function words.
+    #[serde(rename = "functionWords", default)]
+    pub function_words: Option<Vec<String>>,
+
+    /// Which kinds of text are never mutated.
+    #[serde(rename = "protect", default)]
+    pub protect: ProtectionOptions,
+
+    /// How far apart mutations must be.
+    #[serde(rename = "spacing", default)]
+    pub spacing: Option<SpacingOptions>,
+
+    /// Per-kind settings, keyed by the kind's name. These take precedence over the `allow*` flags.
+    #[serde(rename = "mutations", default)]
+    pub mutations: HashMap<String, MutationKindOptions>,
+}
+
+impl MutationRequestOptions {
+    /// The `allow*` flag corresponding to the mutation kind with the given name, if it was given.
+    fn legacy_flag(&self, name: &str) -> Option<bool> {
+        match name {
+            SwapLetters::NAME => self.allow_swaps,
+            RemovePunctuation::NAME => self.allow_punctuation_removal,
+            ReplaceHomophone::NAME => self.allow_homophones,
+            ReplaceConfusable::NAME => self.allow_confusables,
+            _ => None,
+        }
+    }
+
+    /// Fills in any setting the request leaves unspecified from the given preset.
+    pub(crate) fn apply_preset(&mut self, preset: &Preset) {
+        self.mutation_rate.get_or_insert(preset.mutation_rate);
+        self.strategy.get_or_insert(preset.strategy);
+        self.spacing.get_or_insert(preset.spacing);
+
+        for &(name, weight) in preset.kinds {
+            let enabled = self.legacy_flag(name).unwrap_or(true);
+            let options = self.mutations.entry(name.to_string()).or_default();
+
+            options.enabled.get_or_insert(enabled);
+            options.weight.get_or_insert(weight);
+        }
+    }
+
+    /// The mutation rate, defaulting to 0.
+    pub fn mutation_rate(&self) -> f32 {
+        self.mutation_rate.unwrap_or_default()
+    }
+
+    /// The strategy used to choose mutations, defaulting to [`MutationStrategy::Sentences`].
+    pub fn strategy(&self) -> MutationStrategy {
+        self.strategy.unwrap_or_default()
+    }
+
+    /// The minimum distance between mutations, defaulting to none.
+    pub fn spacing(&self) -> SpacingOptions {
+        self.spacing.unwrap_or_default()
+    }
+
+    /// Whether the mutation kind with the given name should be applied.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.mutations
+            .get(name)
+            .and_then(|o| o.enabled)
+            .or_else(|| self.legacy_flag(name))
+            .unwrap_or(false)
+    }
+
+    /// The relative weight of the mutation kind with the given name, defaulting to 1.
+    pub fn weight(&self, name: &str) -> f32 {
+        self.mutations
+            .get(name)
+            .and_then(|o| o.weight)
+            .unwrap_or(1.0)
+    }
+
+    /// The fewest mutations of the kind with the given name to apply, defaulting to 0.
+    pub fn min_count(&self, name: &str) -> usize {
+        self.mutations.get(name).and_then(|o| o.min).unwrap_or(0)
+    }
+
+    /// The most mutations of the kind with the given name to apply, if limited.
+    pub fn max_count(&self, name: &str) -> Option<usize> {
+        self.mutations.get(name).and_then(|o| o.max)
+    }
+}
+
+/// The unit in which offsets into a passage are measured.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OffsetUnit {
+    /// Unicode scalar values, i.e. Rust `char`s.
+    #[default]
+    Chars,
+
+    /// UTF-8 bytes.
+    Utf8,
+
+    /// UTF-16 code units, as used to index JavaScript strings.
+    Utf16,
+}
+
+/// The format of a passage.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Plain text, all of which may be mutated.
+    #[default]
+    PlainText,
+
+    /// A Markdown document. Only prose is mutated, leaving syntax and code untouched, and
+    /// offsets refer to the Markdown source.
+    Markdown,
+
+    /// An HTML document. Only text content is mutated, leaving tags, attributes, character
+    /// references and elements such as `<script>` and `<code>` untouched, and offsets refer to
+    /// the HTML source.
+    Html,
+}
+
+/// A keyboard layout, used to find the keys next to each letter.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+
+    /// The German layout, which swaps Y and Z and adds umlauts.
+    Qwertz,
+
+    /// The French layout.
+    Azerty,
+
+    Dvorak,
+}
+
+/// The minimum distance between any two mutations. Zero, the default, imposes no minimum.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct SpacingOptions {
+    /// The fewest characters between two mutations.
+    #[serde(rename = "minChars")]
+    pub min_chars: usize,
+
+    /// The fewest whole words between two mutations.
+    #[serde(rename = "minWords")]
+    pub min_words: usize,
+}
+
+/// Determines which regions of a passage are protected from mutation.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ProtectionOptions {
+    /// Web addresses, such as `https://example.com`.
+    #[serde(rename = "urls")]
+    pub urls: bool,
+
+    /// Email addresses.
+    #[serde(rename = "emails")]
+    pub emails: bool,
+
+    /// Inline code between backticks.
+    #[serde(rename = "code")]
+    pub code: bool,
+
+    /// Clock times and numeric dates, such as `10:30` or `2024-01-31`.
+    #[serde(rename = "timesAndDates")]
+    pub times_and_dates: bool,
+
+    /// Amounts of money and numbers with units, such as `$1,000` or `3.5 kg`.
+    #[serde(rename = "numbers")]
+    pub numbers: bool,
+
+    /// Passages in double quotes. Off by default.
+    #[serde(rename = "quotes")]
+    pub quotes: bool,
+}
+
+impl Default for ProtectionOptions {
+    fn default() -> Self {
+        ProtectionOptions {
+            urls: true,
+            emails: true,
+            code: true,
+            times_and_dates: true,
+            numbers: true,
+            quotes: false,
+        }
+    }
+}
+
+/// Represents a request to undo some or all of the mutations applied to a passage.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct RestoreRequest {
+    /// The mutated passage, as returned by a mutation request.
+    pub mutated_text: String,
+
+    /// The mutations to undo. Any mutations left out stay in the passage.
+    pub mutations: Vec<RestoreRequestItem>,
+
+    /// The unit used for the offsets in `mutations`.
+    #[serde(rename = "offsetUnit", default)]
+    pub offset_unit: OffsetUnit,
+}
+
+/// A mutation to undo, as described by a [`MutationResponseItem`].
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct RestoreRequestItem {
+    /// The index where, in the mutated passage, this mutation begins.
+    pub start: usize,
+
+    /// The index where, in the mutated passage, this mutation ends (exclusive).
+    pub end: usize,
+
+    /// The text this mutation replaced in the original passage.
+    pub original: String,
+
+    /// The text this mutation put in its place, which must match the mutated passage.
+    pub replacement: String,
+
+    /// The type of mutation.
+    pub r#type: String,
+}
+
+/// Settings for a single mutation kind.
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+pub struct MutationKindOptions {
+    /// Whether the kind is enabled. If omitted, the matching `allow*` flag is used.
+    #[serde(rename = "enabled", default)]
+    pub enabled: Option<bool>,
+
+    /// How likely the kind is to be chosen relative to the other enabled kinds, regardless of
+    /// how many candidates each kind finds. Defaults to 1.
+    #[serde(rename = "weight", default)]
+    pub weight: Option<f32>,
+
+    /// The fewest mutations of this kind to apply. If there aren't enough candidates, the
+    /// response reports a shortfall.
+    #[serde(rename = "min", default)]
+    pub min: Option<usize>,
+
+    /// The most mutations of this kind to apply.
+    #[serde(rename = "max", default)]
+    pub max: Option<usize>,
+}
+
+/// Determines how mutations are chosen from the candidates found in a passage.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MutationStrategy {
+    /// Mutates `mutation_rate` of the passage's sentences, applying at most one mutation to each.
+    #[default]
+    Sentences,
+
+    /// Mutates `mutation_rate` of every possible mutation found in the passage.
+    Candidates,
+
+    /// Mutates as many sentences as [`MutationStrategy::Sentences`] would, but spreads the
+    /// mutations evenly across the passage's paragraphs.
+    Paragraphs,
 }
 
 // Responses
@@ -40,31 +338,108 @@ pub struct MutationResponse {
 
     /// A collection indicating what mutations were applied, and where.
     pub mutations: Vec<MutationResponseItem>,
+
+    /// Present when fewer mutations were applied than the request's `count` or a kind's `min`
+    /// asked for, because the passage didn't have enough candidates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortfall: Option<MutationShortfallResponse>,
+}
+
+/// Describes how the applied mutations fell short of what the request asked for.
+#[derive(serde::Serialize)]
+pub struct MutationShortfallResponse {
+    /// The `count` given in the request, if any.
+    pub requested: Option<usize>,
+
+    /// The number of mutations actually applied.
+    pub applied: usize,
+
+    /// The kinds which fell short of their `min`.
+    pub kinds: Vec<MutationKindShortfallResponse>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MutationKindShortfallResponse {
+    /// The kind which fell short.
+    pub r#type: MutationResponseType,
+
+    /// The `min` given for this kind in the request.
+    pub minimum: usize,
+
+    /// The number of mutations of this kind actually applied.
+    pub applied: usize,
 }
 
 #[derive(serde::Serialize)]
 pub struct MutationResponseItem {
-    /// The character-based index where, in the mutated passage, this mutation begins.
+    /// The index where, in the mutated passage, this mutation begins.
+    /// Measured in the `offsetUnit` given in the request.
     pub start: usize,
 
-    /// The character-based index where, in the mutated passage, this mutation ends.
+    /// The index where, in the mutated passage, this mutation ends (exclusive).
+    /// Equal to `start` when the mutation removed text without replacing it.
     pub end: usize,
 
+    /// The index where, in the original passage, the mutated text began.
+    pub original_start: usize,
+
+    /// The index where, in the original passage, the mutated text ended (exclusive).
+    pub original_end: usize,
+
+    /// The text this mutation replaced, e.g. the letters before a swap or the removed
+    /// punctuation mark.
+    pub original: String,
+
+    /// The text this mutation put in its place. Empty when the mutation removed text.
+    pub replacement: String,
+
     /// The type of mutation indicated by this item.
     pub r#type: MutationResponseType,
 }
 
-/// A mutation that can be applied to text
+/// A mutation that can be applied to text, identified by the name of its kind (e.g. `SwapLetters`).
+#[derive(serde::Serialize)]
+#[serde(transparent)]
+pub struct MutationResponseType(pub String);
+
+/// Represents a passage with some or all of its mutations undone.
+#[derive(serde::Serialize)]
+pub struct RestoreResponse {
+    /// The restored passage.
+    pub text: String,
+}
+
+/// Describes a preset supported by the server.
 #[derive(serde::Serialize)]
-pub enum MutationResponseType {
-    /// Swaps a letter with the next letter.
-    SwapLetters,
+pub struct PresetResponse {
+    /// The name used to select this preset in requests.
+    pub name: String,
+
+    /// A human-readable explanation of the preset.
+    pub description: String,
+
+    /// The mutation rate the preset applies.
+    #[serde(rename = "mutationRate")]
+    pub mutation_rate: f32,
+
+    /// The strategy the preset applies.
+    pub strategy: MutationStrategy,
+
+    /// The minimum distance between mutations the preset applies.
+    pub spacing: SpacingOptions,
+
+    /// The settings the preset applies to each mutation kind, in the same form as requests.
+    pub mutations: HashMap<String, MutationKindOptions>,
+}
 
-    /// Removes punctuation.
-    RemovePunctuation,
+/// Describes a mutation kind supported by the server.
+#[derive(serde::Serialize)]
+pub struct MutationKindResponse {
+    /// The name used to refer to this kind in requests and responses.
+    pub name: String,
 
-    /// Replaces a word with a homophone.
-    ReplaceHomophone,
+    /// A human-readable explanation of the kind.
+    pub description: String,
 }
 
 // Domain types
@@ -74,10 +449,49 @@ pub struct MutationResult {
 
     /// A collection indicating what mutations were applied, and where.
     pub mutations: Vec<Mutation>,
+
+    /// How the applied mutations fell short of the requested count or minimums, if they did.
+    pub shortfall: Option<Shortfall>,
 }
 
-pub(crate) enum Mutation {
-    SwapLetters(usize),             // Swap with next letter
-    RemovePunctuation(usize),       // Remove punctuation at index
-    ReplaceHomophone(usize, usize), // Replace word at index with length
+/// A mutation which has been applied to a passage.
+pub(crate) struct Mutation {
+    /// The name of the kind which produced this mutation.
+    pub kind: &'static str,
+
+    /// Where the mutation lies in the mutated passage.
+    pub span: Span,
+
+    /// The text the mutation replaced, in the original passage.
+    pub original_span: Span,
+
+    /// The text covered by `original_span` in the original passage.
+    pub original: String,
+
+    /// The text covered by `span` in the mutated passage.
+    pub replacement: String,
+}
+
+/// How the mutations applied to a passage fell short of what was asked for.
+pub(crate) struct Shortfall {
+    /// The exact number of mutations asked for, if any.
+    pub requested: Option<usize>,
+
+    /// The number of mutations applied.
+    pub applied: usize,
+
+    /// The kinds which fell short of their minimum.
+    pub kinds: Vec<KindShortfall>,
+}
+
+/// A mutation kind which fell short of its minimum.
+pub(crate) struct KindShortfall {
+    /// The name of the kind.
+    pub kind: &'static str,
+
+    /// The fewest mutations of this kind asked for.
+    pub minimum: usize,
+
+    /// The number of mutations of this kind applied.
+    pub applied: usize,
 }
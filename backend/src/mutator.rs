@@ -1,34 +1,261 @@
 use rand::rngs::StdRng;
-use rand::{SeedableRng, seq::SliceRandom};
+use rand::{
+    Rng, SeedableRng,
+    seq::{IndexedRandom, SliceRandom},
+};
+use std::borrow::Cow;
+use std::ops::Range;
 use std::sync::Arc;
 use tracing::{debug, info, trace};
 
-use crate::homophones::HomophoneSets;
-use crate::models::{Mutation, MutationResult};
+use crate::conflicts::ConflictResolver;
+use crate::edits::{Edit, apply_edits};
+use crate::kinds::MutationKind;
+use crate::models::{
+    InputFormat, KindShortfall, Mutation, MutationResult, MutationStrategy, ProtectionOptions,
+    Shortfall, SpacingOptions,
+};
+use crate::protected::ProtectedRegions;
+use crate::sentences::{split_paragraphs, split_sentences};
+use crate::span::Span;
+use crate::{html, markdown};
+
+/// A mutation kind enabled for a request, along with its request-specific settings.
+#[derive(Clone)]
+pub(crate) struct EnabledKind {
+    pub(crate) kind: Arc<dyn MutationKind>,
+
+    /// How likely this kind is to be picked, relative to the other enabled kinds.
+    pub(crate) weight: f32,
+
+    /// The fewest mutations of this kind to apply, if enough candidates exist.
+    pub(crate) min: usize,
+
+    /// The most mutations of this kind to apply, if limited.
+    pub(crate) max: Option<usize>,
+}
+
+/// A mutation chosen for a passage, positioned within the original text.
+struct PlannedMutation {
+    /// The index of the mutation's kind within [`TextMutator::kinds`].
+    kind: usize,
+    span: Span,
+}
+
+/// Candidate mutations grouped by kind. Mutations are drawn by first picking a kind according to
+/// the kinds' weights, then picking uniformly among that kind's candidates, so a kind's share of
+/// the mutations doesn't depend on how many candidates it found.
+struct CandidatePool {
+    by_kind: Vec<Vec<PlannedMutation>>,
+}
+
+impl CandidatePool {
+    fn new(num_kinds: usize) -> Self {
+        CandidatePool {
+            by_kind: (0..num_kinds).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    fn push(&mut self, mutation: PlannedMutation) {
+        self.by_kind[mutation.kind].push(mutation);
+    }
+
+    /// Groups candidates by the range of text they start in, dropping any outside every range.
+    /// `ranges` must be sorted and must not overlap.
+    fn group_by(
+        ranges: &[Range<usize>],
+        num_kinds: usize,
+        possible_mutations: Vec<PlannedMutation>,
+    ) -> Vec<CandidatePool> {
+        let mut pools: Vec<CandidatePool> = ranges
+            .iter()
+            .map(|_| CandidatePool::new(num_kinds))
+            .collect();
+
+        for mutation in possible_mutations {
+            let position = mutation.span.start;
+
+            let index = ranges.partition_point(|r| r.end <= position);
+            if ranges.get(index).is_some_and(|r| r.contains(&position)) {
+                pools[index].push(mutation);
+            }
+        }
+
+        pools
+    }
+
+    fn is_empty(&self) -> bool {
+        self.by_kind.iter().all(Vec::is_empty)
+    }
+
+    /// Removes and returns a random candidate of one of the `allowed` kinds, or `None` if no
+    /// candidate of an allowed kind with a positive weight remains.
+    fn draw(
+        &mut self,
+        kinds: &[EnabledKind],
+        allowed: &[bool],
+        rng: &mut StdRng,
+    ) -> Option<PlannedMutation> {
+        let available: Vec<usize> = (0..self.by_kind.len())
+            .filter(|&i| !self.by_kind[i].is_empty() && kinds[i].weight > 0.0 && allowed[i])
+            .collect();
+
+        let &kind = available.choose_weighted(rng, |&i| kinds[i].weight).ok()?;
+
+        let candidates = &mut self.by_kind[kind];
+        let index = rng.random_range(0..candidates.len());
+        Some(candidates.swap_remove(index))
+    }
+}
+
+/// The mutations chosen so far for a passage, along with what's needed to keep choosing them
+/// within the target count and each kind's minimum and maximum.
+struct Selection<'a> {
+    kinds: &'a [EnabledKind],
+    target: usize,
+
+    /// How many candidates of each kind have yet to be drawn.
+    remaining: Vec<usize>,
+
+    /// How many mutations of each kind have been chosen.
+    chosen: Vec<usize>,
+
+    resolver: ConflictResolver<'a>,
+    mutations: Vec<PlannedMutation>,
+}
+
+impl<'a> Selection<'a> {
+    /// Starts a selection aiming for `target` mutations, raised if needed to fit every kind's
+    /// minimum. `pools` must hold every candidate that may be drawn.
+    fn new(
+        kinds: &'a [EnabledKind],
+        target: usize,
+        pools: &[CandidatePool],
+        resolver: ConflictResolver<'a>,
+    ) -> Self {
+        let mut remaining = vec![0; kinds.len()];
+        for pool in pools {
+            for (count, candidates) in remaining.iter_mut().zip(&pool.by_kind) {
+                *count += candidates.len();
+            }
+        }
+
+        let minimums: usize = kinds.iter().map(|k| k.min).sum();
+
+        Selection {
+            kinds,
+            target: target.max(minimums),
+            remaining,
+            chosen: vec![0; kinds.len()],
+            resolver,
+            mutations: Vec::new(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.mutations.len() >= self.target
+    }
+
+    /// Whether each kind can still be drawn and is below its minimum.
+    fn below_minimum(&self) -> Vec<bool> {
+        (0..self.kinds.len())
+            .map(|i| {
+                self.remaining[i] > 0
+                    && self.kinds[i].weight > 0.0
+                    && self.chosen[i] < self.kinds[i].min
+            })
+            .collect()
+    }
+
+    /// Whether each kind is below its maximum.
+    fn below_maximum(&self) -> Vec<bool> {
+        self.kinds
+            .iter()
+            .zip(&self.chosen)
+            .map(|(kind, &chosen)| kind.max.is_none_or(|max| chosen < max))
+            .collect()
+    }
+
+    /// Whether more mutations can be chosen than are needed to meet the outstanding minimums.
+    fn has_spare_room(&self, below_minimum: &[bool]) -> bool {
+        let outstanding: usize = (0..self.kinds.len())
+            .filter(|&i| below_minimum[i])
+            .map(|i| self.kinds[i].min - self.chosen[i])
+            .sum();
+
+        outstanding < self.target - self.mutations.len()
+    }
+
+    /// Chooses the first candidate drawn from `pool` which doesn't conflict with an earlier
+    /// choice, returning whether one was found.
+    ///
+    /// Kinds below their minimum are drawn first. Other kinds below their maximum are only drawn
+    /// if `pool` has no candidates of the former, and there's room left to meet the minimums.
+    fn take(&mut self, pool: &mut CandidatePool, rng: &mut StdRng) -> bool {
+        loop {
+            let below_minimum = self.below_minimum();
+
+            let drawn = pool.draw(self.kinds, &below_minimum, rng).or_else(|| {
+                self.has_spare_room(&below_minimum)
+                    .then(|| pool.draw(self.kinds, &self.below_maximum(), rng))
+                    .flatten()
+            });
+
+            let Some(mutation) = drawn else {
+                return false;
+            };
+
+            self.remaining[mutation.kind] -= 1;
+
+            if self.resolver.try_reserve(mutation.span) {
+                self.chosen[mutation.kind] += 1;
+                self.mutations.push(mutation);
+                return true;
+            }
+        }
+    }
+}
 
 /// Applies mutations to text
 pub struct TextMutator {
     mutation_rate: f32,
+
+    /// The exact number of mutations to apply, overriding `mutation_rate`.
+    count: Option<usize>,
+
+    /// Which kinds of text are never mutated.
+    protection: ProtectionOptions,
+
+    format: InputFormat,
+
+    /// How far apart mutations must be.
+    spacing: SpacingOptions,
+
+    /// Whether to wrap each mutation in a `<mark>` element, for HTML passages.
+    mark_mutations: bool,
+
+    strategy: MutationStrategy,
     rng: StdRng,
-    swap_letters: bool,
-    remove_punctuation: bool,
-    use_homophones: bool,
-    homophones: Arc<HomophoneSets>,
+    kinds: Vec<EnabledKind>,
 }
 
 impl TextMutator {
     pub(crate) fn new(
         mutation_rate: f32,
+        strategy: MutationStrategy,
         seed: Option<u64>,
-        swap_letters: bool,
-        remove_punctuation: bool,
-        homophones: bool,
-        homophone_sets: Arc<HomophoneSets>,
+        kinds: Vec<EnabledKind>,
     ) -> Self {
-        info!("Creating TextMutator with mutation_rate={}", mutation_rate);
+        info!(
+            "Creating TextMutator with mutation_rate={}, strategy={:?}",
+            mutation_rate, strategy
+        );
         debug!(
-            "Mutation flags: swap_letters={}, remove_punctuation={}, homophones={}",
-            swap_letters, remove_punctuation, homophones
+            "Enabled mutation kinds: {:?}",
+            kinds
+                .iter()
+                .map(|k| (k.kind.name(), k.weight))
+                .collect::<Vec<_>>()
         );
 
         let rng = if let Some(seed_val) = seed {
@@ -41,69 +268,96 @@ impl TextMutator {
 
         TextMutator {
             mutation_rate,
+            count: None,
+            protection: ProtectionOptions::default(),
+            format: InputFormat::default(),
+            spacing: SpacingOptions::default(),
+            mark_mutations: false,
+            strategy,
             rng,
-            swap_letters,
-            remove_punctuation,
-            use_homophones: homophones,
-            homophones: homophone_sets,
+            kinds,
         }
     }
 
-    fn find_possible_mutations(&self, text: &str) -> Vec<Mutation> {
+    /// Applies exactly `count` mutations rather than `mutation_rate` of them, whenever enough
+    /// non-conflicting candidates exist.
+    pub(crate) fn with_count(mut self, count: Option<usize>) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets which kinds of text are never mutated, in place of the defaults.
+    pub(crate) fn with_protection(mut self, protection: ProtectionOptions) -> Self {
+        self.protection = protection;
+        self
+    }
+
+    /// Treats passages as the given format, only mutating their prose.
+    pub(crate) fn with_format(mut self, format: InputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Keeps mutations at least as far apart as `spacing` requires, even if that means applying
+    /// fewer of them.
+    pub(crate) fn with_spacing(mut self, spacing: SpacingOptions) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Wraps each mutation in a `<mark data-type="...">` element naming its kind, so that the
+    /// mutated passage doubles as an answer key. Only applies to HTML passages, and the reported
+    /// spans cover just the replacement inside each element.
+    pub(crate) fn with_marks(mut self, mark_mutations: bool) -> Self {
+        self.mark_mutations = mark_mutations;
+        self
+    }
+
+    fn find_possible_mutations(&self, text: &str) -> Vec<PlannedMutation> {
         trace!(
             "Finding possible mutations in text of length {}",
             text.len()
         );
-        let mut mutations = Vec::new();
-        let chars: Vec<char> = text.chars().collect();
-
-        // Find possible letter swaps
-        if self.swap_letters {
-            trace!("Looking for possible letter swaps");
-            for i in 0..chars.len().saturating_sub(1) {
-                if chars[i].is_alphabetic() && chars[i + 1].is_alphabetic() {
-                    mutations.push(Mutation::SwapLetters(i));
+        let detect = |options: ProtectionOptions| {
+            let mut protected = ProtectedRegions::detect(text, options);
+            match self.format {
+                InputFormat::PlainText => {}
+                InputFormat::Markdown => protected.extend(markdown::syntax_regions(text)),
+                InputFormat::Html => {
+                    protected.extend(html::markup(text).into_iter().map(|m| m.span));
                 }
             }
-        }
-
-        // Find punctuation that could be removed
-        if self.remove_punctuation {
-            trace!("Looking for punctuation to remove");
-            for (i, c) in chars.iter().enumerate() {
-                if c.is_ascii_punctuation() {
-                    mutations.push(Mutation::RemovePunctuation(i));
-                }
-            }
-        }
+            protected
+        };
+        let default_protected = detect(self.protection);
 
-        // Find homophones that could be replaced
-        if self.use_homophones {
-            trace!("Looking for homophones to replace");
-            let words: Vec<&str> = text.split_whitespace().collect();
-            let mut char_index = 0;
+        // Kinds look for candidates in the text content alone, so that markup neither hides
+        // where sentences start nor passes for words. The offsets are the same as in `text`.
+        let content = self.segmentation_text(text);
 
-            for word in words {
-                // Skip past whitespace to get to the word
-                while char_index < text.len() && !text[char_index..].starts_with(word) {
-                    char_index += 1;
-                }
+        let mut mutations = Vec::new();
 
-                // Strip punctuation from word for homophone lookup
-                let clean_word: String = word
-                    .chars()
-                    .filter(|c| c.is_alphabetic() || c == &'\'')
-                    .collect();
-
-                if !clean_word.is_empty()
-                    && self.homophones.find_matching_set(&clean_word).is_some()
-                {
-                    trace!("Found homophone candidate: '{}'", clean_word);
-                    mutations.push(Mutation::ReplaceHomophone(char_index, word.len()));
-                }
+        for (index, enabled) in self.kinds.iter().enumerate() {
+            trace!("Looking for candidates of kind {}", enabled.kind.name());
+
+            // Kinds which lift some of the protection get regions of their own.
+            let options = enabled.kind.protection(self.protection);
+            let own_protected;
+            let protected = if options == self.protection {
+                &default_protected
+            } else {
+                own_protected = detect(options);
+                &own_protected
+            };
 
-                char_index += word.len();
-            }
+            mutations.extend(
+                enabled
+                    .kind
+                    .find_candidates(&content)
+                    .into_iter()
+                    .filter(|&span| !protected.covers(span))
+                    .map(|span| PlannedMutation { kind: index, span }),
+            );
         }
 
         debug!("Found {} possible mutations", mutations.len());
@@ -116,10 +370,84 @@ impl TextMutator {
 
         debug_assert!(self.mutation_rate >= 0.0);
 
+        // Select which mutations to apply
+        let mut selected_mutations = match self.strategy {
+            MutationStrategy::Candidates => self.select_from_candidates(text, possible_mutations),
+            MutationStrategy::Sentences => self.select_per_sentence(text, possible_mutations),
+            MutationStrategy::Paragraphs => self.select_per_paragraph(text, possible_mutations),
+        };
+
+        if selected_mutations.is_empty() {
+            info!("No mutations to apply");
+            return MutationResult {
+                mutated_text: text.to_string(),
+                mutations: vec![],
+                shortfall: self.shortfall(&[]),
+            };
+        }
+
+        selected_mutations.sort_by_key(|m| m.span.start);
+
+        let (result, applied_mutations) = self.apply_mutations(text, selected_mutations);
+
+        MutationResult {
+            mutated_text: result,
+            shortfall: self.shortfall(&applied_mutations),
+            mutations: applied_mutations,
+        }
+    }
+
+    /// Describes how the applied mutations fall short of the requested count or of any kind's
+    /// minimum, or returns `None` if every requirement was met.
+    fn shortfall(&self, mutations: &[Mutation]) -> Option<Shortfall> {
+        let kinds: Vec<KindShortfall> = self
+            .kinds
+            .iter()
+            .filter_map(|enabled| {
+                let name = enabled.kind.name();
+                let applied = mutations.iter().filter(|m| m.kind == name).count();
+
+                (applied < enabled.min).then_some(KindShortfall {
+                    kind: name,
+                    minimum: enabled.min,
+                    applied,
+                })
+            })
+            .collect();
+
+        let short_of_count = self.count.is_some_and(|count| mutations.len() < count);
+
+        if !short_of_count && kinds.is_empty() {
+            return None;
+        }
+
+        debug!(
+            "Applied {} mutations, short of the requested {:?}",
+            mutations.len(),
+            self.count
+        );
+
+        Some(Shortfall {
+            requested: self.count,
+            applied: mutations.len(),
+            kinds,
+        })
+    }
+
+    /// Picks `mutation_rate` of all possible mutations, or exactly `count` of them, drawing each
+    /// according to the kinds' weights. Candidates which conflict with an already-selected
+    /// mutation are skipped.
+    fn select_from_candidates(
+        &mut self,
+        text: &str,
+        possible_mutations: Vec<PlannedMutation>,
+    ) -> Vec<PlannedMutation> {
         #[allow(clippy::cast_possible_truncation)]
         #[allow(clippy::cast_sign_loss)]
         #[allow(clippy::cast_precision_loss)]
-        let num_mutations = (possible_mutations.len() as f32 * self.mutation_rate).floor() as usize;
+        let num_mutations = self.count.unwrap_or_else(|| {
+            (possible_mutations.len() as f32 * self.mutation_rate).floor() as usize
+        });
 
         debug!(
             "Planning to apply {} mutations out of {} possible",
@@ -127,118 +455,325 @@ impl TextMutator {
             possible_mutations.len()
         );
 
-        if possible_mutations.is_empty() || num_mutations == 0 {
-            info!("No mutations to apply");
-            return MutationResult {
-                mutated_text: text.to_string(),
-                mutations: vec![],
-            };
+        let mut pool = CandidatePool::new(self.kinds.len());
+        for mutation in possible_mutations {
+            pool.push(mutation);
         }
 
-        // Select which mutations to apply
-        let mut selected_mutations = possible_mutations;
-        selected_mutations.shuffle(&mut self.rng);
-        selected_mutations.truncate(num_mutations);
-
-        // Sort by position to apply from end to beginning (to avoid index shifts)
-        selected_mutations.sort_by(|a, b| {
-            let pos_a = match a {
-                Mutation::SwapLetters(i)
-                | Mutation::RemovePunctuation(i)
-                | Mutation::ReplaceHomophone(i, _) => *i,
-            };
+        let mut selection = Selection::new(
+            &self.kinds,
+            num_mutations,
+            std::slice::from_ref(&pool),
+            ConflictResolver::with_spacing(text, self.spacing),
+        );
 
-            let pos_b = match b {
-                Mutation::SwapLetters(i)
-                | Mutation::RemovePunctuation(i)
-                | Mutation::ReplaceHomophone(i, _) => *i,
-            };
+        while !selection.is_complete() && selection.take(&mut pool, &mut self.rng) {}
 
-            pos_b.cmp(&pos_a) // Reverse order
-        });
+        selection.mutations
+    }
 
-        let result = self.apply_mutations(text, &selected_mutations);
+    /// Picks `mutation_rate` of the passage's sentences and applies one mutation to each.
+    ///
+    /// Within a chosen sentence, a mutation type is picked according to the weights of the enabled
+    /// types which have candidates there, so that letter swaps don't crowd out rarer types.
+    ///
+    /// With an exact `count`, sentences are chosen as many times as it takes to reach the count,
+    /// so a short passage may have more than one mutation in some sentences.
+    fn select_per_sentence(
+        &mut self,
+        text: &str,
+        possible_mutations: Vec<PlannedMutation>,
+    ) -> Vec<PlannedMutation> {
+        let sentences = split_sentences(&self.segmentation_text(text));
+        let pools = CandidatePool::group_by(&sentences, self.kinds.len(), possible_mutations);
+
+        let num_mutations = self
+            .count
+            .unwrap_or_else(|| self.sentence_target(sentences.len()));
 
-        MutationResult {
-            mutated_text: result,
-            mutations: selected_mutations,
+        debug!(
+            "Planning to apply {} mutations across {} sentences",
+            num_mutations,
+            sentences.len()
+        );
+
+        // Only an exact count may revisit sentences which already have a mutation.
+        let revisit = self.count.is_some();
+        self.select_round_robin(text, pools, num_mutations, revisit)
+    }
+
+    /// Picks as many mutations as [`TextMutator::select_per_sentence`] would, or exactly `count`,
+    /// dealing them out one paragraph at a time so that every paragraph gets a similar share.
+    fn select_per_paragraph(
+        &mut self,
+        text: &str,
+        possible_mutations: Vec<PlannedMutation>,
+    ) -> Vec<PlannedMutation> {
+        let segmentation = self.segmentation_text(text);
+        let paragraphs = split_paragraphs(&segmentation);
+        let num_sentences = split_sentences(&segmentation).len();
+        let pools = CandidatePool::group_by(&paragraphs, self.kinds.len(), possible_mutations);
+
+        let num_mutations = self
+            .count
+            .unwrap_or_else(|| self.sentence_target(num_sentences));
+
+        debug!(
+            "Planning to apply {} mutations across {} paragraphs",
+            num_mutations,
+            paragraphs.len()
+        );
+
+        self.select_round_robin(text, pools, num_mutations, true)
+    }
+
+    /// The text to split into sentences and paragraphs, which for HTML is just its text content.
+    fn segmentation_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        match self.format {
+            // Tags are blanked out so that they don't run blocks together.
+            InputFormat::Html => Cow::Owned(html::mask(text, &html::markup(text))),
+            InputFormat::PlainText | InputFormat::Markdown => Cow::Borrowed(text),
         }
     }
 
-    fn apply_mutations(&mut self, text: &str, selected_mutations: &Vec<Mutation>) -> String {
-        let mut result = text.to_string();
-        let mut actual_mutations = 0;
-        debug!("Applying mutations from end to beginning to avoid index shifts");
+    /// The number of mutations to apply to a passage of `num_sentences` sentences, at
+    /// `mutation_rate` of them.
+    fn sentence_target(&self, num_sentences: usize) -> usize {
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        #[allow(clippy::cast_precision_loss)]
+        let num_mutations = (num_sentences as f32 * self.mutation_rate).round() as usize;
 
-        for mutation in selected_mutations {
-            match mutation {
-                Mutation::SwapLetters(i) => {
-                    let i = *i;
-
-                    let mut chars: Vec<char> = result.chars().collect();
-                    if i + 1 < chars.len() {
-                        trace!(
-                            "Swapping letters at positions {} and {}: '{}' and '{}'",
-                            i,
-                            i + 1,
-                            chars[i],
-                            chars[i + 1]
-                        );
-                        chars.swap(i, i + 1);
-                        result = chars.into_iter().collect();
-                        actual_mutations += 1;
-                    }
-                }
-                Mutation::RemovePunctuation(i) => {
-                    let i = *i;
-
-                    let mut chars: Vec<char> = result.chars().collect();
-                    if i < chars.len() && chars[i].is_ascii_punctuation() {
-                        trace!("Removing punctuation '{}' at position {}", chars[i], i);
-                        chars.remove(i);
-                        result = chars.into_iter().collect();
-                        actual_mutations += 1;
-                    }
-                }
-                Mutation::ReplaceHomophone(i, len) => {
-                    let i = *i;
-                    if i + len <= result.len() {
-                        let word = &result[i..i + len];
-                        let clean_word: String = word
-                            .chars()
-                            .filter(|c| c.is_alphabetic() || c == &'\'')
-                            .collect();
-
-                        if let Some(alternative) =
-                            self.homophones.get_alternative(&clean_word, &mut self.rng)
-                        {
-                            trace!(
-                                "Replacing homophone '{}' with '{}'",
-                                clean_word, alternative
-                            );
-
-                            // Preserve trailing punctuation if any
-                            let trailing_punct: String =
-                                word.chars().filter(char::is_ascii_punctuation).collect();
-
-                            let replacement = alternative + &trailing_punct;
-                            result = result[..i].to_string() + &replacement + &result[i + len..];
-                            actual_mutations += 1;
-                        }
-                    }
+        // Any non-zero rate should mutate something, even in very short passages.
+        if self.mutation_rate > 0.0 {
+            num_mutations.max(1)
+        } else {
+            num_mutations
+        }
+    }
+
+    /// Takes one mutation from each pool in turn, in a random order, until `num_mutations` have
+    /// been chosen. With `revisit`, the pools are passed over again for as long as that makes
+    /// progress; otherwise each pool contributes at most one mutation.
+    fn select_round_robin(
+        &mut self,
+        text: &str,
+        pools: Vec<CandidatePool>,
+        num_mutations: usize,
+        revisit: bool,
+    ) -> Vec<PlannedMutation> {
+        let mut eligible: Vec<CandidatePool> =
+            pools.into_iter().filter(|pool| !pool.is_empty()).collect();
+
+        trace!("{} groups have candidates", eligible.len());
+
+        eligible.shuffle(&mut self.rng);
+
+        let mut selection = Selection::new(
+            &self.kinds,
+            num_mutations,
+            &eligible,
+            ConflictResolver::with_spacing(text, self.spacing),
+        );
+
+        loop {
+            let selected_before = selection.mutations.len();
+
+            for pool in &mut eligible {
+                if selection.is_complete() {
+                    break;
                 }
+
+                selection.take(pool, &mut self.rng);
+            }
+
+            let progressed = selection.mutations.len() > selected_before;
+            if !revisit || selection.is_complete() || !progressed {
+                break;
             }
         }
 
-        info!("Applied {} mutations", actual_mutations);
-        result
+        selection.mutations
+    }
+
+    /// Applies the selected mutations to the original text in a single pass, returning the
+    /// mutated text and the mutations which took effect. Mutations must be sorted by position
+    /// and must not overlap.
+    fn apply_mutations(
+        &mut self,
+        text: &str,
+        selected_mutations: Vec<PlannedMutation>,
+    ) -> (String, Vec<Mutation>) {
+        let mark = self.mark_mutations && self.format == InputFormat::Html;
+
+        let mut applied = Vec::with_capacity(selected_mutations.len());
+        let mut replacements = Vec::with_capacity(selected_mutations.len());
+        let mut edits = Vec::with_capacity(selected_mutations.len());
+
+        for mutation in selected_mutations {
+            let kind = &self.kinds[mutation.kind].kind;
+
+            if let Some(replacement) = kind.apply(text, mutation.span, &mut self.rng) {
+                let (open, close) = if mark {
+                    (format!("<mark data-type=\"{}\">", kind.name()), "</mark>")
+                } else {
+                    (String::new(), "")
+                };
+
+                edits.push(Edit {
+                    span: mutation.span,
+                    replacement: format!("{open}{replacement}{close}"),
+                });
+                replacements.push((replacement, open.len(), close.len()));
+                applied.push(mutation);
+            }
+        }
+
+        info!("Applying {} mutations", edits.len());
+
+        let (result, spans) = apply_edits(text, &edits);
+
+        let mutations = applied
+            .into_iter()
+            .zip(replacements)
+            .zip(spans)
+            .map(|((mutation, (replacement, open, close)), span)| Mutation {
+                kind: self.kinds[mutation.kind].kind.name(),
+                span: Span::new(span.start + open, span.end - close),
+                original_span: mutation.span,
+                original: text[mutation.span.range()].to_string(),
+                replacement,
+            })
+            .collect();
+
+        (result, mutations)
+    }
+
+    /// Undoes `mutations` in a mutated passage, returning the passage as it was before they were
+    /// applied. Passing every mutation from a [`MutationResult`] restores the original exactly;
+    /// passing only some of them leaves the others in place.
+    ///
+    /// Fails if a mutation's replacement doesn't match the passage at its span, or if two
+    /// mutations overlap.
+    pub(crate) fn restore(
+        mutated_text: &str,
+        mutations: &[Mutation],
+    ) -> Result<String, RestoreError> {
+        let mut sorted: Vec<&Mutation> = mutations.iter().collect();
+        sorted.sort_by_key(|m| m.span.start);
+
+        let mut edits = Vec::with_capacity(sorted.len());
+        let mut position = 0;
+
+        for mutation in sorted {
+            if mutation.span.start < position {
+                return Err(RestoreError::Overlapping {
+                    start: mutation.span.start,
+                });
+            }
+
+            if mutated_text.get(mutation.span.range()) != Some(mutation.replacement.as_str()) {
+                return Err(RestoreError::Mismatch {
+                    start: mutation.span.start,
+                    expected: mutation.replacement.clone(),
+                });
+            }
+
+            edits.push(Edit {
+                span: mutation.span,
+                replacement: mutation.original.clone(),
+            });
+            position = mutation.span.end;
+        }
+
+        Ok(apply_edits(mutated_text, &edits).0)
+    }
+}
+
+/// The reasons a set of mutations can't be undone.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RestoreError {
+    /// The passage doesn't contain a mutation's replacement at the mutation's position.
+    Mismatch { start: usize, expected: String },
+
+    /// A mutation overlaps an earlier one.
+    Overlapping { start: usize },
+}
+
+impl RestoreError {
+    /// Converts the byte offset of the offending mutation with `convert`, so it can be reported
+    /// in the unit the client sent it in.
+    pub(crate) fn map_offset(self, convert: impl FnOnce(usize) -> usize) -> Self {
+        match self {
+            RestoreError::Mismatch { start, expected } => RestoreError::Mismatch {
+                start: convert(start),
+                expected,
+            },
+            RestoreError::Overlapping { start } => RestoreError::Overlapping {
+                start: convert(start),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestoreError::Mismatch { start, expected } => write!(
+                f,
+                "The mutated text doesn't contain {expected:?} at offset {start}"
+            ),
+            RestoreError::Overlapping { start } => {
+                write!(f, "The mutation at offset {start} overlaps another")
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Mutation; // Use the internal Mutation enum
+    use crate::confusables::ConfusableSets;
+    use crate::homophones::HomophoneSets;
+    use crate::kinds::{
+        LowercaseProperNoun, LowercaseSentenceStart, MutationRegistry, RemovePunctuation,
+        ReplaceHomophone, SwapLetters,
+    };
+
+    fn create_weighted_kinds(weight: impl Fn(&str) -> f32) -> Vec<EnabledKind> {
+        let registry = MutationRegistry::with_defaults(
+            Arc::new(HomophoneSets::new_for_tests()),
+            Arc::new(ConfusableSets::new_for_tests()),
+        );
+
+        registry
+            .enabled(|name| weight(name) > 0.0)
+            .into_iter()
+            .map(|kind| EnabledKind {
+                weight: weight(kind.name()),
+                kind,
+                min: 0,
+                max: None,
+            })
+            .collect()
+    }
+
+    fn create_kinds(
+        swap_letters: bool,
+        remove_punctuation: bool,
+        homophones: bool,
+    ) -> Vec<EnabledKind> {
+        create_weighted_kinds(|name| {
+            let enabled = match name {
+                SwapLetters::NAME => swap_letters,
+                RemovePunctuation::NAME => remove_punctuation,
+                ReplaceHomophone::NAME => homophones,
+                _ => false,
+            };
+
+            if enabled { 1.0 } else { 0.0 }
+        })
+    }
 
     // Helper to create a mutator with specific options and a fixed seed
     fn create_test_mutator(
@@ -250,22 +785,51 @@ mod tests {
         // Use a fixed seed for deterministic tests
         TextMutator::new(
             mutation_rate,
+            MutationStrategy::Candidates,
             Some(42),
-            swap_letters,
-            remove_punctuation,
-            homophones,
-            Arc::new(HomophoneSets::new_for_tests()),
+            create_kinds(swap_letters, remove_punctuation, homophones),
         )
     }
 
+    fn is_mutation(
+        mutator: &TextMutator,
+        mutation: &PlannedMutation,
+        kind: &str,
+        start: usize,
+        end: usize,
+    ) -> bool {
+        mutator.kinds[mutation.kind].kind.name() == kind && mutation.span == Span::new(start, end)
+    }
+
+    fn is_applied(
+        mutation: &Mutation,
+        kind: &str,
+        original_start: usize,
+        original_end: usize,
+    ) -> bool {
+        mutation.kind == kind && mutation.original_span == Span::new(original_start, original_end)
+    }
+
     #[test]
     fn test_find_possible_mutations_swap_only() {
         let mutator = create_test_mutator(1.0, true, false, false);
         let text = "abc";
         let mutations = mutator.find_possible_mutations(text);
         assert_eq!(mutations.len(), 2); // ab, bc
-        assert!(matches!(mutations[0], Mutation::SwapLetters(0)));
-        assert!(matches!(mutations[1], Mutation::SwapLetters(1)));
+        assert!(is_mutation(
+            &mutator,
+            &mutations[0],
+            SwapLetters::NAME,
+            0,
+            2
+        ));
+        assert!(is_mutation(
+            &mutator,
+            &mutations[1],
+            SwapLetters::NAME,
+            1,
+            3
+        ));
     }
 
     #[test]
@@ -274,9 +838,27 @@ mod tests {
         let text = "a,b.c!";
         let mutations = mutator.find_possible_mutations(text);
         assert_eq!(mutations.len(), 3); // , . !
-        assert!(matches!(mutations[0], Mutation::RemovePunctuation(1)));
-        assert!(matches!(mutations[1], Mutation::RemovePunctuation(3)));
-        assert!(matches!(mutations[2], Mutation::RemovePunctuation(5)));
+        assert!(is_mutation(
+            &mutator,
+            &mutations[0],
+            RemovePunctuation::NAME,
+            1,
+            2
+        ));
+        assert!(is_mutation(
+            &mutator,
+            &mutations[1],
+            RemovePunctuation::NAME,
+            3,
+            4
+        ));
+        assert!(is_mutation(
+            &mutator,
+            &mutations[2],
+            RemovePunctuation::NAME,
+            5,
+            6
+        ));
     }
 
     #[test]
@@ -285,7 +867,13 @@ mod tests {
         let text = "your text"; // "your" is a homophone candidate
         let mutations = mutator.find_possible_mutations(text);
         assert_eq!(mutations.len(), 1); // your
-        assert!(matches!(mutations[0], Mutation::ReplaceHomophone(0, 4))); // "your" starts at 0, length 4
+        assert!(is_mutation(
+            &mutator,
+            &mutations[0],
+            ReplaceHomophone::NAME,
+            0,
+            4
+        )); // "your" starts at 0, length 4
     }
 
     #[test]
@@ -314,21 +902,16 @@ mod tests {
 
         let result = mutator.mutate(text);
 
-        assert_eq!(result.mutated_text, "cab");
-        assert_eq!(result.mutations.len(), 2);
+        // The two possible swaps overlap, so only one of them can be applied.
+        assert_eq!(result.mutations.len(), 1);
 
-        assert!(
-            result
-                .mutations
-                .iter()
-                .any(|m| matches!(m, Mutation::SwapLetters(0)))
-        );
-        assert!(
-            result
-                .mutations
-                .iter()
-                .any(|m| matches!(m, Mutation::SwapLetters(1)))
-        );
+        let mutation = &result.mutations[0];
+        if is_applied(mutation, SwapLetters::NAME, 0, 2) {
+            assert_eq!(result.mutated_text, "bac");
+        } else {
+            assert!(is_applied(mutation, SwapLetters::NAME, 1, 3));
+            assert_eq!(result.mutated_text, "acb");
+        }
     }
 
     #[test]
@@ -347,19 +930,19 @@ mod tests {
             result
                 .mutations
                 .iter()
-                .any(|m| matches!(m, Mutation::RemovePunctuation(1)))
+                .any(|m| is_applied(m, RemovePunctuation::NAME, 1, 2))
         );
         assert!(
             result
                 .mutations
                 .iter()
-                .any(|m| matches!(m, Mutation::RemovePunctuation(3)))
+                .any(|m| is_applied(m, RemovePunctuation::NAME, 3, 4))
         );
         assert!(
             result
                 .mutations
                 .iter()
-                .any(|m| matches!(m, Mutation::RemovePunctuation(5)))
+                .any(|m| is_applied(m, RemovePunctuation::NAME, 5, 6))
         );
     }
 
@@ -378,13 +961,13 @@ mod tests {
             result
                 .mutations
                 .iter()
-                .any(|m| matches!(m, Mutation::ReplaceHomophone(0, 4)))
+                .any(|m| is_applied(m, ReplaceHomophone::NAME, 0, 4))
         );
         assert!(
             result
                 .mutations
                 .iter()
-                .any(|m| matches!(m, Mutation::ReplaceHomophone(10, 2)))
+                .any(|m| is_applied(m, ReplaceHomophone::NAME, 10, 12))
         );
     }
 
@@ -394,15 +977,17 @@ mod tests {
         let text = "It's your text!";
 
         let result = mutator.mutate(text);
-        assert_eq!(result.mutations.len(), 11);
-        assert_ne!(result.mutated_text, text);
+        // With the fixed seed, three of the eleven candidates survive conflict resolution.
+        assert_eq!(result.mutated_text, "Its yoru tetx!");
+        assert_eq!(result.mutations.len(), 3);
+        assert_no_conflicts(&result.mutations);
 
         // No homophones for simplicity
         let mut mutator_simple = create_test_mutator(1.0, true, true, false);
         let text_simple = "Test.";
         let result_simple = mutator_simple.mutate(text_simple);
-        assert_eq!(result_simple.mutated_text, "tTes");
-        assert_eq!(result_simple.mutations.len(), 4);
+        assert_eq!(result_simple.mutated_text, "Tset");
+        assert_eq!(result_simple.mutations.len(), 2);
     }
 
     #[test]
@@ -412,9 +997,648 @@ mod tests {
         let text = "It's your text!";
         let result = mutator.mutate(text);
 
-        assert_eq!(result.mutations.len(), 5);
+        assert_eq!(result.mutated_text, "Its yoru tetx!");
+        assert_eq!(result.mutations.len(), 3);
+        assert_no_conflicts(&result.mutations);
+    }
 
-        assert_ne!(result.mutated_text, text);
+    fn assert_no_conflicts(mutations: &[Mutation]) {
+        let mut ranges: Vec<(usize, usize)> = mutations
+            .iter()
+            .map(|m| (m.original_span.start, m.original_span.end))
+            .collect();
+        ranges.sort_unstable();
+
+        for pair in ranges.windows(2) {
+            assert!(pair[0].1 < pair[1].0, "mutations {pair:?} conflict");
+        }
+    }
+
+    #[test]
+    fn test_mutate_never_overlaps() {
+        let text = "Their cat's toy, it's here. Your dog is there too!";
+
+        for seed in 0..50 {
+            for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+                let mut mutator =
+                    TextMutator::new(1.0, strategy, Some(seed), create_kinds(true, true, true));
+                let result = mutator.mutate(text);
+                assert!(!result.mutations.is_empty());
+                assert_no_conflicts(&result.mutations);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_reports_spans_in_mutated_text() {
+        let mut mutator = create_test_mutator(1.0, false, false, true);
+        let result = mutator.mutate("your text to test");
+        assert_eq!(result.mutated_text, "you're text two test");
+
+        let spans: Vec<_> = result
+            .mutations
+            .iter()
+            .map(|m| {
+                (
+                    m.span.start,
+                    m.span.end,
+                    m.original_span.start,
+                    m.original_span.end,
+                )
+            })
+            .collect();
+        assert_eq!(spans, vec![(0, 6, 0, 4), (12, 15, 10, 12)]);
+    }
+
+    #[test]
+    fn test_mutate_reports_removals_as_empty_spans() {
+        let mut mutator = create_test_mutator(1.0, false, true, false);
+        let result = mutator.mutate("a,b.c!");
+        assert_eq!(result.mutated_text, "abc");
+
+        let spans: Vec<_> = result.mutations.iter().map(|m| m.span).collect();
+        assert_eq!(
+            spans,
+            vec![Span::new(1, 1), Span::new(2, 2), Span::new(3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_mutate_spans_reconstruct_original() {
+        let text = "Were you there? Yes, your car's over there: it's too late!";
+
+        for seed in 0..50 {
+            let mut mutator = TextMutator::new(
+                0.5,
+                MutationStrategy::Candidates,
+                Some(seed),
+                create_kinds(true, true, true),
+            );
+            let result = mutator.mutate(text);
+
+            let mutated = &result.mutated_text;
+
+            // Splicing the original text back over each mutated span must restore the input.
+            let mut restored = String::new();
+            let mut mutated_position = 0;
+            for m in &result.mutations {
+                assert_eq!(
+                    m.span.start - mutated_position,
+                    m.original_span.start - restored.len()
+                );
+                restored.push_str(&mutated[mutated_position..m.span.start]);
+                restored.push_str(&text[m.original_span.range()]);
+                mutated_position = m.span.end;
+            }
+            restored.push_str(&mutated[mutated_position..]);
+
+            assert_eq!(restored, text);
+        }
+    }
+
+    #[test]
+    fn test_mutate_long_text() {
+        let text = "Were you there? Your car's over there: it's too late! ".repeat(3000);
+        assert!(text.chars().count() > 150_000);
+
+        for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+            let mut mutator =
+                TextMutator::new(1.0, strategy, Some(42), create_kinds(true, true, true));
+            let result = mutator.mutate(&text);
+
+            assert!(result.mutations.len() >= 3000);
+            assert_no_conflicts(&result.mutations);
+
+            for m in &result.mutations {
+                assert_ne!(
+                    &result.mutated_text[m.span.range()],
+                    &text[m.original_span.range()]
+                );
+            }
+        }
+    }
+
+    fn count_of_kind(result: &MutationResult, kind: &str) -> usize {
+        result.mutations.iter().filter(|m| m.kind == kind).count()
+    }
+
+    #[test]
+    fn test_weights_ignore_candidate_counts() {
+        // Each sentence has one homophone but dozens of possible swaps.
+        let text = "Your extraordinarily complicated sentence. ".repeat(100);
+
+        // Low enough rates that neither kind runs out of candidates.
+        for (strategy, rate) in [
+            (MutationStrategy::Candidates, 0.03),
+            (MutationStrategy::Sentences, 0.5),
+        ] {
+            let kinds = create_weighted_kinds(|name| match name {
+                SwapLetters::NAME | ReplaceHomophone::NAME => 1.0,
+                _ => 0.0,
+            });
+            let mut mutator = TextMutator::new(rate, strategy, Some(42), kinds);
+            let result = mutator.mutate(&text);
+
+            let homophones = count_of_kind(&result, ReplaceHomophone::NAME);
+            let swaps = count_of_kind(&result, SwapLetters::NAME);
+            assert!(
+                homophones * 3 > swaps && swaps * 3 > homophones,
+                "{strategy:?}: {homophones} homophones, {swaps} swaps"
+            );
+        }
+    }
+
+    #[test]
+    fn test_weights_are_relative() {
+        let text = "Your extraordinarily complicated sentence. ".repeat(100);
+
+        let kinds = create_weighted_kinds(|name| match name {
+            SwapLetters::NAME => 1.0,
+            ReplaceHomophone::NAME => 3.0,
+            _ => 0.0,
+        });
+        let mut mutator = TextMutator::new(1.0, MutationStrategy::Sentences, Some(42), kinds);
+        let result = mutator.mutate(&text);
+
+        let homophones = count_of_kind(&result, ReplaceHomophone::NAME);
+        let swaps = count_of_kind(&result, SwapLetters::NAME);
+        assert_eq!(homophones + swaps, 100);
+        assert!((60..=90).contains(&homophones), "{homophones} homophones");
+    }
+
+    #[test]
+    fn test_zero_weight_is_never_chosen() {
+        let registry = MutationRegistry::with_defaults(
+            Arc::new(HomophoneSets::new_for_tests()),
+            Arc::new(ConfusableSets::new_for_tests()),
+        );
+        let kinds = registry
+            .enabled(|_| true)
+            .into_iter()
+            .map(|kind| EnabledKind {
+                weight: if kind.name() == SwapLetters::NAME {
+                    0.0
+                } else {
+                    1.0
+                },
+                kind,
+                min: 0,
+                max: None,
+            })
+            .collect();
+
+        let mut mutator = TextMutator::new(1.0, MutationStrategy::Candidates, Some(42), kinds);
+        let result = mutator.mutate("Your text, to test!");
+
+        assert!(!result.mutations.is_empty());
+        assert_eq!(count_of_kind(&result, SwapLetters::NAME), 0);
+    }
+
+    #[test]
+    fn test_count_is_exact() {
+        let text = "The cat sat. The dog ran. Your bird flew. Their fish swam.";
+
+        for strategy in [
+            MutationStrategy::Candidates,
+            MutationStrategy::Sentences,
+            MutationStrategy::Paragraphs,
+        ] {
+            for count in [0, 1, 4, 8] {
+                let mut mutator =
+                    TextMutator::new(0.1, strategy, Some(42), create_kinds(true, true, true))
+                        .with_count(Some(count));
+                let result = mutator.mutate(text);
+
+                assert_eq!(result.mutations.len(), count, "{strategy:?}");
+                assert!(result.shortfall.is_none());
+                assert_no_conflicts(&result.mutations);
+            }
+        }
+    }
+
+    #[test]
+    fn test_paragraphs_share_mutations_evenly() {
+        let text = "The cat sat. The dog ran. Your bird flew.\n\n\
+                    Their fish swam. The cow slept. Its calf ate.\n\n\
+                    The hen laid. The pig rolled. The goat climbed.";
+
+        for seed in 0..20 {
+            let mut mutator = TextMutator::new(
+                0.1,
+                MutationStrategy::Paragraphs,
+                Some(seed),
+                create_kinds(true, true, true),
+            )
+            .with_count(Some(6));
+            let result = mutator.mutate(text);
+
+            assert_eq!(result.mutations.len(), 6);
+            for paragraph in split_paragraphs(text) {
+                let inside = result
+                    .mutations
+                    .iter()
+                    .filter(|m| paragraph.contains(&m.original_span.start))
+                    .count();
+                assert_eq!(inside, 2, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_paragraphs_rate_counts_sentences() {
+        let text = "One fish. Two fish.\n\nRed fish. Blue fish.";
+        let mut mutator = TextMutator::new(
+            0.5,
+            MutationStrategy::Paragraphs,
+            Some(42),
+            create_kinds(true, false, false),
+        );
+
+        let result = mutator.mutate(text);
+        assert_eq!(result.mutations.len(), 2);
+        assert!(result.mutations[0].original_span.start < 19);
+        assert!(result.mutations[1].original_span.start > 19);
+    }
+
+    #[test]
+    fn test_spacing_keeps_mutations_apart() {
+        let text = "The quick brown fox jumps over the lazy dog, and the cat naps in the sun.";
+        let spacing = SpacingOptions {
+            min_chars: 10,
+            min_words: 2,
+        };
+
+        for seed in 0..20 {
+            for strategy in [MutationStrategy::Candidates, MutationStrategy::Paragraphs] {
+                let mut mutator =
+                    TextMutator::new(1.0, strategy, Some(seed), create_kinds(true, true, true))
+                        .with_count(Some(20))
+                        .with_spacing(spacing);
+                let result = mutator.mutate(text);
+
+                assert!(result.mutations.len() > 1);
+                assert!(result.shortfall.is_some());
+
+                for pair in result.mutations.windows(2) {
+                    let gap = &text[pair[0].original_span.end..pair[1].original_span.start];
+                    assert!(gap.chars().count() >= 10, "{gap:?}");
+                    assert!(gap.split_whitespace().count() >= 2, "{gap:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_reports_shortfall() {
+        for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+            let mut mutator =
+                TextMutator::new(0.1, strategy, Some(42), create_kinds(false, true, false))
+                    .with_count(Some(5));
+            let result = mutator.mutate("a,b.c!");
+
+            assert_eq!(result.mutations.len(), 3);
+            let shortfall = result.shortfall.unwrap();
+            assert_eq!(shortfall.requested, Some(5));
+            assert_eq!(shortfall.applied, 3);
+            assert!(shortfall.kinds.is_empty());
+        }
+    }
+
+    fn create_bounded_kinds(min: usize, max: Option<usize>) -> Vec<EnabledKind> {
+        // Homophones are bounded, while swaps are plentiful and unbounded.
+        create_kinds(true, false, true)
+            .into_iter()
+            .map(|kind| {
+                if kind.kind.name() == ReplaceHomophone::NAME {
+                    EnabledKind { min, max, ..kind }
+                } else {
+                    kind
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kind_minimums_are_met() {
+        let text = "Your extraordinarily complicated sentence. ".repeat(20);
+
+        for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+            for seed in 0..10 {
+                let mut mutator =
+                    TextMutator::new(0.0, strategy, Some(seed), create_bounded_kinds(6, None))
+                        .with_count(Some(8));
+                let result = mutator.mutate(&text);
+
+                assert_eq!(result.mutations.len(), 8);
+                assert!(count_of_kind(&result, ReplaceHomophone::NAME) >= 6);
+                assert!(result.shortfall.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_kind_minimums_raise_rate_target() {
+        let mut mutator = TextMutator::new(
+            0.0,
+            MutationStrategy::Candidates,
+            Some(42),
+            create_bounded_kinds(2, None),
+        );
+        let result = mutator.mutate("Your car, your rules.");
+
+        assert_eq!(count_of_kind(&result, ReplaceHomophone::NAME), 2);
+        assert!(result.shortfall.is_none());
+    }
+
+    #[test]
+    fn test_kind_maximums_are_respected() {
+        let text = "Your extraordinarily complicated sentence. ".repeat(20);
+
+        for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+            let mut mutator =
+                TextMutator::new(1.0, strategy, Some(42), create_bounded_kinds(0, Some(2)))
+                    .with_count(Some(15));
+            let result = mutator.mutate(&text);
+
+            assert_eq!(result.mutations.len(), 15);
+            assert_eq!(count_of_kind(&result, ReplaceHomophone::NAME), 2);
+        }
+    }
+
+    #[test]
+    fn test_kind_minimum_reports_shortfall() {
+        let mut mutator = TextMutator::new(
+            1.0,
+            MutationStrategy::Candidates,
+            Some(42),
+            create_bounded_kinds(3, None),
+        );
+        let result = mutator.mutate("Your car.");
+
+        let shortfall = result.shortfall.unwrap();
+        assert_eq!(shortfall.requested, None);
+        assert_eq!(shortfall.kinds.len(), 1);
+        assert_eq!(shortfall.kinds[0].kind, ReplaceHomophone::NAME);
+        assert_eq!(shortfall.kinds[0].minimum, 3);
+        assert_eq!(shortfall.kinds[0].applied, 1);
+    }
+
+    #[test]
+    fn test_mutate_reports_only_applied_mutations() {
+        // "its" is a candidate, but a set with no alternatives can never be applied.
+        let homophones = HomophoneSets {
+            sets: vec![vec!["its".to_string()]],
+        };
+        let registry = MutationRegistry::with_defaults(
+            Arc::new(homophones),
+            Arc::new(ConfusableSets::new_for_tests()),
+        );
+        let kinds = registry
+            .enabled(|name| name == ReplaceHomophone::NAME)
+            .into_iter()
+            .map(|kind| EnabledKind {
+                kind,
+                weight: 1.0,
+                min: 0,
+                max: None,
+            })
+            .collect();
+
+        let mut mutator = TextMutator::new(1.0, MutationStrategy::Candidates, Some(42), kinds);
+        let result = mutator.mutate("its fine");
+
+        assert_eq!(result.mutated_text, "its fine");
+        assert!(result.mutations.is_empty());
+    }
+
+    #[test]
+    fn test_mutations_record_original_and_replacement() {
+        let mut mutator = create_test_mutator(1.0, false, true, true);
+        let result = mutator.mutate("your text, to test");
+
+        for m in &result.mutations {
+            assert_eq!(m.original, &"your text, to test"[m.original_span.range()]);
+            assert_eq!(m.replacement, &result.mutated_text[m.span.range()]);
+        }
+
+        assert!(
+            result
+                .mutations
+                .iter()
+                .any(|m| m.original == "," && m.replacement.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_restore_round_trips() {
+        let text = "Were you there? Yes, your car's over there: it's too late!";
+
+        for seed in 0..50 {
+            for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+                let mut mutator =
+                    TextMutator::new(1.0, strategy, Some(seed), create_kinds(true, true, true));
+                let result = mutator.mutate(text);
+
+                let restored = TextMutator::restore(&result.mutated_text, &result.mutations);
+                assert_eq!(restored.as_deref(), Ok(text));
+            }
+        }
+    }
+
+    #[test]
+    fn test_restore_single_mutation() {
+        let mut mutator = create_test_mutator(1.0, false, true, false);
+        let result = mutator.mutate("a,b.c!");
+        assert_eq!(result.mutated_text, "abc");
+
+        // Fixing only the removed full stop leaves the other two errors in place.
+        let full_stop = result.mutations.iter().position(|m| m.original == ".");
+        let fixed = &result.mutations[full_stop.unwrap()..=full_stop.unwrap()];
+
+        assert_eq!(
+            TextMutator::restore(&result.mutated_text, fixed).as_deref(),
+            Ok("ab.c")
+        );
+    }
+
+    #[test]
+    fn test_restore_rejects_mismatched_text() {
+        let mut mutator = create_test_mutator(1.0, false, false, true);
+        let result = mutator.mutate("your text");
+
+        assert_eq!(
+            TextMutator::restore("a different text", &result.mutations),
+            Err(RestoreError::Mismatch {
+                start: 0,
+                expected: "you're".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_mutate_skips_protected_regions() {
+        let text = "Email bob@example.com or visit https://their.example.com/your-car at 10:30.";
+        let protected = [
+            "bob@example.com",
+            "https://their.example.com/your-car",
+            "10:30",
+        ];
+
+        for seed in 0..20 {
+            let mut mutator = TextMutator::new(
+                1.0,
+                MutationStrategy::Candidates,
+                Some(seed),
+                create_kinds(true, true, true),
+            );
+            let result = mutator.mutate(text);
+            assert!(!result.mutations.is_empty());
+
+            for region in protected {
+                assert!(result.mutated_text.contains(region), "{region} was mutated");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_protection_can_be_disabled() {
+        let protection = ProtectionOptions {
+            times_and_dates: false,
+            ..ProtectionOptions::default()
+        };
+        let mut mutator = create_test_mutator(1.0, false, true, false).with_protection(protection);
+
+        assert_eq!(mutator.mutate("10:30").mutated_text, "1030");
+    }
+
+    #[test]
+    fn test_mutate_markdown_only_touches_prose() {
+        let source = "# Your *first* draft\n\nSee [their site](https://example.com/a-b), \
+                      it's `too.late()`!\n\n```\nyour_code(there);\n```\n";
+
+        let mut syntax = ProtectedRegions::default();
+        syntax.extend(markdown::syntax_regions(source));
+
+        for seed in 0..20 {
+            for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+                let mut mutator =
+                    TextMutator::new(1.0, strategy, Some(seed), create_kinds(true, true, true))
+                        .with_format(InputFormat::Markdown);
+                let result = mutator.mutate(source);
+                assert!(!result.mutations.is_empty());
+
+                for m in &result.mutations {
+                    assert!(
+                        !syntax.covers(m.original_span),
+                        "{:?} is syntax",
+                        m.original
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_html_only_touches_text() {
+        let source = "<h1 title=\"Your title\">Your first draft</h1><p>See <a href=\"x.html\">\
+                      their site</a>, it&apos;s <code>too.late()</code>!</p>\
+                      <script>let your = there;</script>";
+
+        let mut markup = ProtectedRegions::default();
+        markup.extend(html::markup(source).into_iter().map(|m| m.span));
+
+        for seed in 0..20 {
+            for strategy in [MutationStrategy::Candidates, MutationStrategy::Sentences] {
+                let mut mutator =
+                    TextMutator::new(1.0, strategy, Some(seed), create_kinds(true, true, true))
+                        .with_format(InputFormat::Html);
+                let result = mutator.mutate(source);
+                assert!(!result.mutations.is_empty());
+
+                for m in &result.mutations {
+                    assert!(
+                        !markup.covers(m.original_span),
+                        "{:?} is markup",
+                        m.original
+                    );
+                }
+
+                assert_eq!(
+                    TextMutator::restore(&result.mutated_text, &result.mutations).unwrap(),
+                    source
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_html_finds_sentence_starts_in_text_content() {
+        let source = "<p>Hello there.</p><p>Good morning.</p>";
+        let kinds = create_weighted_kinds(|name| match name {
+            LowercaseProperNoun::NAME | LowercaseSentenceStart::NAME => 1.0,
+            _ => 0.0,
+        });
+        let mut mutator = TextMutator::new(1.0, MutationStrategy::Candidates, Some(42), kinds)
+            .with_format(InputFormat::Html);
+
+        let result = mutator.mutate(source);
+        assert_eq!(
+            result.mutated_text,
+            "<p>hello there.</p><p>good morning.</p>"
+        );
+
+        // Each paragraph's first word starts a sentence, rather than following the "p" in a tag.
+        let labels: Vec<(&str, &str)> = result
+            .mutations
+            .iter()
+            .map(|m| (m.original.as_str(), m.kind))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("Hello", LowercaseSentenceStart::NAME),
+                ("Good", LowercaseSentenceStart::NAME)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mutate_html_marks_mutations() {
+        let source = "<p>Hello, world</p>";
+        let mut mutator = TextMutator::new(
+            1.0,
+            MutationStrategy::Candidates,
+            Some(1),
+            create_kinds(false, true, false),
+        )
+        .with_format(InputFormat::Html)
+        .with_marks(true);
+
+        let result = mutator.mutate(source);
+        assert_eq!(
+            result.mutated_text,
+            "<p>Hello<mark data-type=\"RemovePunctuation\"></mark> world</p>"
+        );
+
+        // The span covers the replacement inside the element.
+        let end = result.mutated_text.find("</mark>").unwrap();
+        let m = &result.mutations[0];
+        assert_eq!(m.original, ",");
+        assert_eq!(m.replacement, "");
+        assert_eq!(m.span, Span::new(end, end));
+    }
+
+    #[test]
+    fn test_mutate_plain_text_ignores_marks() {
+        let mut mutator = TextMutator::new(
+            1.0,
+            MutationStrategy::Candidates,
+            Some(1),
+            create_kinds(false, true, false),
+        )
+        .with_marks(true);
+
+        assert_eq!(mutator.mutate("Hello, world").mutated_text, "Hello world");
     }
 
     #[test]
@@ -447,7 +1671,7 @@ mod tests {
         let mut mutator = create_test_mutator(1.0, false, false, true);
         let text = "Were you there?";
         let result = mutator.mutate(text);
-        assert_eq!(result.mutated_text, "We're you they're?");
+        assert_eq!(result.mutated_text, "Where you they're?");
         assert_eq!(result.mutations.len(), 2);
     }
 
@@ -459,4 +1683,78 @@ mod tests {
         assert_eq!(result.mutated_text, "You're car, you're rules.");
         assert_eq!(result.mutations.len(), 2);
     }
+
+    fn create_sentence_mutator(mutation_rate: f32, seed: u64) -> TextMutator {
+        TextMutator::new(
+            mutation_rate,
+            MutationStrategy::Sentences,
+            Some(seed),
+            create_kinds(true, true, true),
+        )
+    }
+
+    #[test]
+    fn test_sentences_full_rate_mutates_each_sentence_once() {
+        let text = "The cat sat. The dog ran! Your bird flew?";
+
+        for seed in 0..20 {
+            let mut mutator = create_sentence_mutator(1.0, seed);
+            let result = mutator.mutate(text);
+            assert_eq!(result.mutations.len(), 3);
+
+            let sentences = split_sentences(text);
+            for sentence in sentences {
+                let in_sentence = result
+                    .mutations
+                    .iter()
+                    .filter(|m| sentence.contains(&m.original_span.start))
+                    .count();
+                assert_eq!(in_sentence, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sentences_rate_is_fraction_of_sentences() {
+        let text = "One fish. Two fish. Red fish. Blue fish.";
+
+        let mut mutator = create_sentence_mutator(0.5, 42);
+        assert_eq!(mutator.mutate(text).mutations.len(), 2);
+
+        let mut mutator = create_sentence_mutator(0.25, 42);
+        assert_eq!(mutator.mutate(text).mutations.len(), 1);
+    }
+
+    #[test]
+    fn test_sentences_small_rate_still_mutates() {
+        let mut mutator = create_sentence_mutator(0.01, 42);
+        let result = mutator.mutate("Just the one sentence here.");
+        assert_eq!(result.mutations.len(), 1);
+    }
+
+    #[test]
+    fn test_sentences_zero_rate() {
+        let mut mutator = create_sentence_mutator(0.0, 42);
+        let text = "The cat sat. The dog ran.";
+        let result = mutator.mutate(text);
+        assert_eq!(result.mutated_text, text);
+        assert!(result.mutations.is_empty());
+    }
+
+    #[test]
+    fn test_sentences_skips_sentences_without_candidates() {
+        let mut mutator = TextMutator::new(
+            1.0,
+            MutationStrategy::Sentences,
+            Some(42),
+            create_kinds(false, false, true),
+        );
+        let text = "Nothing here. Over there. Nothing again.";
+        let result = mutator.mutate(text);
+        assert_eq!(
+            result.mutated_text,
+            "Nothing here. Over their. Nothing again."
+        );
+        assert_eq!(result.mutations.len(), 1);
+    }
 }
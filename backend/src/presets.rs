@@ -0,0 +1,198 @@
+use crate::kinds::{
+    ApostropheError, CapitalizeNoun, DeleteLetter, DoubleSpace, DuplicateLetter, DuplicateWord,
+    InsertPunctuation, JoinWords, KeyboardTypo, LowercaseProperNoun, LowercaseSentenceStart,
+    OmitWord, RemovePunctuation, RemoveSentenceSpace, ReplaceConfusable, ReplaceDeterminer,
+    ReplaceHomophone, SplitCompound, SubstitutePunctuation, SwapLetters,
+};
+use crate::models::{MutationStrategy, SpacingOptions};
+
+/// A named bundle of mutation settings, so every client shares the same idea of difficulty.
+pub(crate) struct Preset {
+    /// The name used to select this preset in requests.
+    pub(crate) name: &'static str,
+
+    /// A human-readable explanation of the preset.
+    pub(crate) description: &'static str,
+
+    /// The mutation rate used unless the request gives its own.
+    pub(crate) mutation_rate: f32,
+
+    /// The strategy used unless the request gives its own.
+    pub(crate) strategy: MutationStrategy,
+
+    /// The minimum distance between mutations used unless the request gives its own.
+    pub(crate) spacing: SpacingOptions,
+
+    /// The enabled mutation kinds and their weights. Kinds not listed are disabled unless the
+    /// request enables them.
+    pub(crate) kinds: &'static [(&'static str, f32)],
+}
+
+/// The presets offered by the server, from easiest to hardest.
+///
+/// Typos such as swapped, missing, doubled or mistyped letters and removed punctuation are easy
+/// to spot, while a homophone or commonly confused word still reads as a real word and a repeated
+/// or missing short word is easily read past, so harder presets favour those as well as mutating
+/// more sentences. Likewise a doubled or missing space is easier to see than two words run
+/// together or a compound split.
+///
+/// Errors are also easier to find when they're far apart, so easier presets spread them out,
+/// while "expert" lets them cluster.
+pub(crate) const PRESETS: &[Preset] = &[
+    Preset {
+        name: "easy",
+        description: "A few obvious errors, mostly typos and missing punctuation.",
+        mutation_rate: 0.1,
+        strategy: MutationStrategy::Paragraphs,
+        spacing: SpacingOptions {
+            min_chars: 0,
+            min_words: 5,
+        },
+        kinds: &[
+            (SwapLetters::NAME, 3.0),
+            (RemovePunctuation::NAME, 2.0),
+            (ReplaceHomophone::NAME, 1.0),
+            (DeleteLetter::NAME, 2.0),
+            (DuplicateLetter::NAME, 2.0),
+            (KeyboardTypo::NAME, 2.0),
+            (LowercaseSentenceStart::NAME, 1.0),
+            (SubstitutePunctuation::NAME, 1.0),
+            (DoubleSpace::NAME, 1.0),
+            (RemoveSentenceSpace::NAME, 1.0),
+        ],
+    },
+    Preset {
+        name: "standard",
+        description: "An even mix of every kind of error.",
+        mutation_rate: 0.2,
+        strategy: MutationStrategy::Sentences,
+        spacing: SpacingOptions {
+            min_chars: 0,
+            min_words: 2,
+        },
+        kinds: &[
+            (SwapLetters::NAME, 1.0),
+            (RemovePunctuation::NAME, 1.0),
+            (ReplaceHomophone::NAME, 1.0),
+            (DeleteLetter::NAME, 1.0),
+            (DuplicateLetter::NAME, 1.0),
+            (KeyboardTypo::NAME, 1.0),
+            (DuplicateWord::NAME, 1.0),
+            (OmitWord::NAME, 1.0),
+            (LowercaseSentenceStart::NAME, 1.0),
+            (CapitalizeNoun::NAME, 1.0),
+            (LowercaseProperNoun::NAME, 1.0),
+            (SubstitutePunctuation::NAME, 1.0),
+            (InsertPunctuation::NAME, 1.0),
+            (DoubleSpace::NAME, 1.0),
+            (RemoveSentenceSpace::NAME, 1.0),
+            (JoinWords::NAME, 1.0),
+            (SplitCompound::NAME, 1.0),
+            (ApostropheError::NAME, 1.0),
+            (ReplaceDeterminer::NAME, 1.0),
+            (ReplaceConfusable::NAME, 1.0),
+        ],
+    },
+    Preset {
+        name: "expert",
+        description: "Frequent, subtle errors, mostly homophones and missing or repeated words.",
+        mutation_rate: 0.35,
+        strategy: MutationStrategy::Sentences,
+        spacing: SpacingOptions {
+            min_chars: 0,
+            min_words: 0,
+        },
+        kinds: &[
+            (SwapLetters::NAME, 1.0),
+            (RemovePunctuation::NAME, 2.0),
+            (ReplaceHomophone::NAME, 4.0),
+            (DeleteLetter::NAME, 1.0),
+            (DuplicateWord::NAME, 2.0),
+            (OmitWord::NAME, 3.0),
+            (CapitalizeNoun::NAME, 1.0),
+            (LowercaseProperNoun::NAME, 1.0),
+            (SubstitutePunctuation::NAME, 2.0),
+            (InsertPunctuation::NAME, 2.0),
+            (JoinWords::NAME, 1.0),
+            (SplitCompound::NAME, 2.0),
+            (ApostropheError::NAME, 2.0),
+            (ReplaceDeterminer::NAME, 2.0),
+            (ReplaceConfusable::NAME, 3.0),
+        ],
+    },
+];
+
+/// Looks up a preset by name, ignoring case.
+pub(crate) fn find_preset(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confusables::ConfusableSets;
+    use crate::homophones::HomophoneSets;
+    use crate::kinds::{MutationRegistry, NumericError};
+    use crate::models::MutationRequestOptions;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_find_preset() {
+        assert_eq!(find_preset("easy").unwrap().name, "easy");
+        assert_eq!(find_preset("Expert").unwrap().name, "expert");
+        assert!(find_preset("impossible").is_none());
+    }
+
+    #[test]
+    fn test_apply_preset_fills_unset_settings() {
+        let easy = find_preset("easy").unwrap();
+
+        let mut options = MutationRequestOptions::default();
+        options.apply_preset(easy);
+        assert_eq!(options.strategy(), MutationStrategy::Paragraphs);
+        assert_eq!(options.spacing(), easy.spacing);
+
+        let mut options = MutationRequestOptions {
+            strategy: Some(MutationStrategy::Candidates),
+            spacing: Some(SpacingOptions::default()),
+            ..Default::default()
+        };
+        options.apply_preset(easy);
+        assert_eq!(options.strategy(), MutationStrategy::Candidates);
+        assert_eq!(options.spacing(), SpacingOptions::default());
+    }
+
+    #[test]
+    fn test_presets_differ_in_spacing() {
+        let easy = find_preset("easy").unwrap();
+        let expert = find_preset("expert").unwrap();
+        assert!(easy.spacing.min_words > expert.spacing.min_words);
+    }
+
+    #[test]
+    fn test_numeric_errors_are_opt_in() {
+        for preset in PRESETS {
+            assert!(
+                preset
+                    .kinds
+                    .iter()
+                    .all(|(name, _)| *name != NumericError::NAME)
+            );
+        }
+    }
+
+    #[test]
+    fn test_presets_use_registered_kinds() {
+        let registry = MutationRegistry::with_defaults(
+            Arc::new(HomophoneSets::new_for_tests()),
+            Arc::new(ConfusableSets::new_for_tests()),
+        );
+
+        for preset in PRESETS {
+            for (name, weight) in preset.kinds {
+                assert!(registry.get(name).is_some(), "{}: {name}", preset.name);
+                assert!(*weight > 0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::models::ProtectionOptions;
+use crate::span::Span;
+
+/// Web addresses, excluding any punctuation which ends the sentence they're in.
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(?:https?://|www\.)[^\s<>"]*[^\s<>".,;:!?)]"#).unwrap());
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[\w.+-]+@[\w-]+(?:\.[\w-]+)+\b").unwrap());
+
+static CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`\n]+`").unwrap());
+
+/// Clock times ("10:30", "9:05:59 pm", "5 p.m.") and numeric dates ("2024-01-31", "31/01/24").
+static TIME_OR_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?ix)
+        \b\d{1,2}(?::\d{2}){1,2}(?:\s?[ap]\.?m\b\.?)?
+        | \b\d{1,2}\s?[ap]\.?m\b\.?
+        | \b\d{4}-\d{1,2}-\d{1,2}\b
+        | \b\d{1,2}[/.-]\d{1,2}[/.-]\d{2,4}\b",
+    )
+    .unwrap()
+});
+
+/// Amounts of money and numbers followed by a unit, such as "$1,000.50", "3.5 kg" or "20%".
+static NUMBER_WITH_UNIT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?x)
+        [$€£¥]\s?\d+(?:[.,]\d+)*
+        | \d+(?:[.,]\d+)*
+          (?: \s?(?:%|°[CF]?)
+            | \s?(?i:km|kg|mg|cm|mm|ml|kb|mb|gb|tb|mph|kph|hz|khz|mhz|ghz|lbs?|oz|ft)\b
+            | (?i:m|g|l|s|h)\b
+          )",
+    )
+    .unwrap()
+});
+
+static QUOTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""[^"\n]*"|“[^”\n]*”|«[^»\n]*»"#).unwrap());
+
+/// Parts of a passage which must never be mutated, such as URLs and email addresses.
+///
+/// Errors in these read as noise rather than as the kind of mistake a proofreader should catch.
+#[derive(Default)]
+pub(crate) struct ProtectedRegions {
+    /// Sorted, non-overlapping spans.
+    spans: Vec<Span>,
+}
+
+impl ProtectedRegions {
+    /// Finds the regions of `text` protected by the given options.
+    pub(crate) fn detect(text: &str, options: ProtectionOptions) -> Self {
+        let patterns: [(bool, &Regex); 6] = [
+            (options.urls, &URL),
+            (options.emails, &EMAIL),
+            (options.code, &CODE),
+            (options.times_and_dates, &TIME_OR_DATE),
+            (options.numbers, &NUMBER_WITH_UNIT),
+            (options.quotes, &QUOTE),
+        ];
+
+        let spans = patterns
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .flat_map(|(_, pattern)| pattern.find_iter(text))
+            .map(|m| Span::new(m.start(), m.end()));
+
+        let mut regions = ProtectedRegions::default();
+        regions.extend(spans);
+        regions
+    }
+
+    /// Protects the given spans in addition to those already protected.
+    pub(crate) fn extend(&mut self, spans: impl IntoIterator<Item = Span>) {
+        let mut spans: Vec<Span> = self.spans.drain(..).chain(spans).collect();
+        spans.sort_unstable();
+
+        for span in spans {
+            match self.spans.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => self.spans.push(span),
+            }
+        }
+    }
+
+    /// Whether any part of `span` lies in a protected region. An empty span is only protected
+    /// if it falls strictly inside a region.
+    pub(crate) fn covers(&self, span: Span) -> bool {
+        let index = self.spans.partition_point(|s| s.end <= span.start);
+        self.spans.get(index).is_some_and(|s| s.start < span.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protected(text: &str, options: ProtectionOptions) -> Vec<&str> {
+        ProtectedRegions::detect(text, options)
+            .spans
+            .into_iter()
+            .map(|span| &text[span.range()])
+            .collect()
+    }
+
+    fn only(set: impl Fn(&mut ProtectionOptions)) -> ProtectionOptions {
+        let mut options = ProtectionOptions {
+            urls: false,
+            emails: false,
+            code: false,
+            times_and_dates: false,
+            numbers: false,
+            quotes: false,
+        };
+        set(&mut options);
+        options
+    }
+
+    #[test]
+    fn test_detect_urls() {
+        let options = only(|o| o.urls = true);
+        assert_eq!(
+            protected(
+                "See https://example.com/a?b=c. Or www.example.org, too.",
+                options
+            ),
+            vec!["https://example.com/a?b=c", "www.example.org"]
+        );
+    }
+
+    #[test]
+    fn test_detect_emails() {
+        let options = only(|o| o.emails = true);
+        assert_eq!(
+            protected("Write to jane.doe+news@mail.example.co.uk today.", options),
+            vec!["jane.doe+news@mail.example.co.uk"]
+        );
+    }
+
+    #[test]
+    fn test_detect_code() {
+        let options = only(|o| o.code = true);
+        assert_eq!(
+            protected("Call `foo.bar()` then `baz`.", options),
+            vec!["`foo.bar()`", "`baz`"]
+        );
+    }
+
+    #[test]
+    fn test_detect_times_and_dates() {
+        let options = only(|o| o.times_and_dates = true);
+        assert_eq!(
+            protected(
+                "Meet at 10:30, or 5 p.m. on 2024-01-31 (31/01/24).",
+                options
+            ),
+            vec!["10:30", "5 p.m.", "2024-01-31", "31/01/24"]
+        );
+    }
+
+    #[test]
+    fn test_detect_numbers_with_units() {
+        let options = only(|o| o.numbers = true);
+        assert_eq!(
+            protected("It costs $1,000.50 for 3.5 kg, up 20%. 12 apples", options),
+            vec!["$1,000.50", "3.5 kg", "20%"]
+        );
+    }
+
+    #[test]
+    fn test_quotes_are_opt_in() {
+        let text = "He said \"their car\" and “your dog”.";
+        assert!(protected(text, ProtectionOptions::default()).is_empty());
+        assert_eq!(
+            protected(text, only(|o| o.quotes = true)),
+            vec!["\"their car\"", "“your dog”"]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_regions_are_merged() {
+        let text = "Mail `bob@example.com` now";
+        assert_eq!(
+            protected(text, ProtectionOptions::default()),
+            vec!["`bob@example.com`"]
+        );
+    }
+
+    #[test]
+    fn test_covers() {
+        let regions = ProtectedRegions {
+            spans: vec![Span::new(2, 5), Span::new(8, 9)],
+        };
+
+        assert!(regions.covers(Span::new(0, 3)));
+        assert!(regions.covers(Span::new(4, 8)));
+        assert!(regions.covers(Span::new(3, 3)));
+        assert!(!regions.covers(Span::new(0, 2)));
+        assert!(!regions.covers(Span::new(5, 8)));
+        assert!(!regions.covers(Span::new(2, 2)));
+        assert!(!regions.covers(Span::new(5, 5)));
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::Range;
+
+/// Abbreviations which end in a full stop but rarely end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "vs", "etc", "e.g", "i.e", "cf",
+    "approx", "no", "fig", "inc", "ltd", "co", "jan", "feb", "mar", "apr", "jun", "jul", "aug",
+    "sep", "sept", "oct", "nov", "dec",
+];
+
+/// Characters which may trail a sentence terminator and still belong to the sentence.
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | ')' | ']' | '}' | '”' | '’' | '»')
+}
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…')
+}
+
+/// Splits a passage into sentences, returning the byte range of each one.
+///
+/// Sentences end at a run of terminators (`.`, `!`, `?`, `…`) followed by whitespace and
+/// something that can start a new sentence, or at a paragraph break. Abbreviations, initials
+/// and decimal numbers do not end a sentence. Leading and trailing whitespace is excluded from
+/// each range, and whitespace-only sentences are dropped.
+pub(crate) fn split_sentences(text: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (_, c) = chars[i];
+
+        if c == '\n' && is_paragraph_break(&chars, i) {
+            push_trimmed(text, start..chars[i].0, &mut sentences);
+            start = chars[i].0;
+            i += 1;
+            continue;
+        }
+
+        if !is_terminator(c) {
+            i += 1;
+            continue;
+        }
+
+        // Consume the whole run of terminators and any closing quotes or brackets.
+        let run_start = i;
+        while i < chars.len() && is_terminator(chars[i].1) {
+            i += 1;
+        }
+        let single_stop = i - run_start == 1 && c == '.';
+        while i < chars.len() && is_closing(chars[i].1) {
+            i += 1;
+        }
+
+        if single_stop && is_abbreviation(&chars, run_start) {
+            continue;
+        }
+
+        let end = chars.get(i).map_or(text.len(), |(b, _)| *b);
+
+        if starts_new_sentence(&chars, i) {
+            push_trimmed(text, start..end, &mut sentences);
+            start = end;
+        }
+    }
+
+    push_trimmed(text, start..text.len(), &mut sentences);
+
+    sentences
+}
+
+/// Splits a passage into paragraphs at blank lines, returning the byte range of each one.
+///
+/// Leading and trailing whitespace is excluded from each range, and whitespace-only paragraphs
+/// are dropped.
+pub(crate) fn split_paragraphs(text: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut paragraphs = Vec::new();
+    let mut start = 0;
+
+    for (i, &(position, c)) in chars.iter().enumerate() {
+        if c == '\n' && is_paragraph_break(&chars, i) {
+            push_trimmed(text, start..position, &mut paragraphs);
+            start = position;
+        }
+    }
+
+    push_trimmed(text, start..text.len(), &mut paragraphs);
+
+    paragraphs
+}
+
+/// Whether the newline at `i` is followed by another newline, with only whitespace between.
+fn is_paragraph_break(chars: &[(usize, char)], i: usize) -> bool {
+    chars[i + 1..]
+        .iter()
+        .take_while(|(_, c)| c.is_whitespace())
+        .any(|(_, c)| *c == '\n')
+}
+
+/// Whether the text following a terminator at `i` looks like the beginning of a new sentence.
+fn starts_new_sentence(chars: &[(usize, char)], i: usize) -> bool {
+    let mut rest = chars[i..].iter().map(|(_, c)| *c);
+
+    match rest.next() {
+        None => return true,
+        Some(c) if !c.is_whitespace() => return false,
+        Some(_) => {}
+    }
+
+    match rest.find(|c| !c.is_whitespace()) {
+        None => true,
+        Some(c) => {
+            c.is_uppercase()
+                || c.is_numeric()
+                || matches!(c, '"' | '\'' | '(' | '[' | '“' | '‘' | '«' | '¿' | '¡')
+        }
+    }
+}
+
+/// Whether the full stop at `i` closes an abbreviation or an initial rather than a sentence.
+fn is_abbreviation(chars: &[(usize, char)], i: usize) -> bool {
+    // Only as far back as the longest abbreviation is looked at, so long runs such as "a.a.a."
+    // don't make each full stop rescan the whole run.
+    let longest = ABBREVIATIONS.iter().map(|a| a.len()).max().unwrap_or(0);
+    let length = chars[..i]
+        .iter()
+        .rev()
+        .take(longest + 1)
+        .take_while(|(_, c)| c.is_alphabetic() || *c == '.')
+        .count();
+
+    if length == 0 || length > longest {
+        return false;
+    }
+
+    let word = &chars[i - length..i];
+
+    // Initials such as the "J." in "J. Smith", but not the pronoun "I".
+    if let [(_, first)] = word {
+        return first.is_uppercase() && *first != 'I';
+    }
+
+    ABBREVIATIONS.iter().any(|abbreviation| {
+        abbreviation.len() == length
+            && abbreviation
+                .chars()
+                .zip(word)
+                .all(|(a, (_, c))| c.eq_ignore_ascii_case(&a))
+    })
+}
+
+fn push_trimmed(text: &str, range: Range<usize>, sentences: &mut Vec<Range<usize>>) {
+    let slice = &text[range.clone()];
+    let leading = slice.len() - slice.trim_start().len();
+    let trailing = slice.len() - slice.trim_end().len();
+
+    if leading == slice.len() {
+        return;
+    }
+
+    sentences.push(range.start + leading..range.end - trailing);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraphs(text: &str) -> Vec<&str> {
+        split_paragraphs(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    fn sentences(text: &str) -> Vec<&str> {
+        split_sentences(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_split_simple_sentences() {
+        assert_eq!(
+            sentences("One fish. Two fish! Red fish? Blue fish."),
+            vec!["One fish.", "Two fish!", "Red fish?", "Blue fish."]
+        );
+    }
+
+    #[test]
+    fn test_split_empty_and_whitespace() {
+        assert!(sentences("").is_empty());
+        assert!(sentences("   \n  ").is_empty());
+    }
+
+    #[test]
+    fn test_split_without_terminator() {
+        assert_eq!(sentences("  no full stop here "), vec!["no full stop here"]);
+    }
+
+    #[test]
+    fn test_split_ignores_abbreviations_and_initials() {
+        assert_eq!(
+            sentences("Mr. Smith met Dr. Jones at 5 p.m. on Friday. J. R. R. Tolkien wrote it."),
+            vec![
+                "Mr. Smith met Dr. Jones at 5 p.m. on Friday.",
+                "J. R. R. Tolkien wrote it."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_ignores_decimals() {
+        assert_eq!(
+            sentences("Pi is roughly 3.14 or so. Nobody knows."),
+            vec!["Pi is roughly 3.14 or so.", "Nobody knows."]
+        );
+    }
+
+    #[test]
+    fn test_split_keeps_closing_quotes() {
+        assert_eq!(
+            sentences("He said \"Stop!\" Then he left. (It was late.) Fine."),
+            vec![
+                "He said \"Stop!\"",
+                "Then he left.",
+                "(It was late.)",
+                "Fine."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_requires_new_sentence_start() {
+        assert_eq!(
+            sentences("Wait... what happened? Who knows!?"),
+            vec!["Wait... what happened?", "Who knows!?"]
+        );
+    }
+
+    #[test]
+    fn test_split_on_paragraph_break() {
+        assert_eq!(
+            sentences("A heading\n\nSome text. More text\nacross lines."),
+            vec!["A heading", "Some text.", "More text\nacross lines."]
+        );
+    }
+
+    #[test]
+    fn test_split_long_dotted_run() {
+        // Each full stop used to rescan the whole run before it, taking quadratic time.
+        let text = "a.".repeat(200_000);
+        assert_eq!(split_sentences(&text), vec![0..text.len()]);
+    }
+
+    #[test]
+    fn test_split_returns_byte_ranges() {
+        let text = "Café au lait. Très bien.";
+        let ranges = split_sentences(text);
+        assert_eq!(ranges, vec![0..14, 15..text.len()]);
+    }
+
+    #[test]
+    fn test_split_paragraphs() {
+        assert_eq!(
+            paragraphs("\n  One. Two.\nThree.\n\n \n\tFour.\r\n\r\nFive.  \n"),
+            vec!["One. Two.\nThree.", "Four.", "Five."]
+        );
+        assert!(paragraphs(" \n\n ").is_empty());
+    }
+}
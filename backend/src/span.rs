@@ -0,0 +1,203 @@
+use std::ops::Range;
+
+use crate::models::OffsetUnit;
+
+/// A half-open region of a passage, measured in UTF-8 bytes.
+///
+/// Spans are always stored as byte offsets so they can slice the passage directly; they are only
+/// converted to the unit requested by the client when building a response.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Span {
+    /// The byte offset where the span begins.
+    pub(crate) start: usize,
+
+    /// The byte offset where the span ends (exclusive).
+    pub(crate) end: usize,
+}
+
+impl Span {
+    pub(crate) fn new(start: usize, end: usize) -> Self {
+        debug_assert!(start <= end, "span {start}..{end} is reversed");
+        Span { start, end }
+    }
+
+    pub(crate) fn range(self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Whether this span overlaps or touches `other`.
+    pub(crate) fn touches(self, other: Span) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// Converts byte offsets within a passage into another [`OffsetUnit`].
+///
+/// Conversions are cheapest when offsets are requested in ascending order, as the converter
+/// resumes scanning from the previous offset rather than from the start of the passage.
+pub(crate) struct OffsetConverter<'a> {
+    text: &'a str,
+    unit: OffsetUnit,
+    last_byte: usize,
+    last_converted: usize,
+}
+
+impl<'a> OffsetConverter<'a> {
+    pub(crate) fn new(text: &'a str, unit: OffsetUnit) -> Self {
+        OffsetConverter {
+            text,
+            unit,
+            last_byte: 0,
+            last_converted: 0,
+        }
+    }
+
+    /// Converts a byte offset, which must lie on a character boundary, into the target unit.
+    pub(crate) fn convert(&mut self, byte: usize) -> usize {
+        debug_assert!(self.text.is_char_boundary(byte));
+
+        if self.unit == OffsetUnit::Utf8 {
+            return byte;
+        }
+
+        if byte < self.last_byte {
+            self.last_byte = 0;
+            self.last_converted = 0;
+        }
+
+        let between = &self.text[self.last_byte..byte];
+        self.last_converted += match self.unit {
+            OffsetUnit::Chars => between.chars().count(),
+            OffsetUnit::Utf16 => between.encode_utf16().count(),
+            OffsetUnit::Utf8 => unreachable!(),
+        };
+        self.last_byte = byte;
+
+        self.last_converted
+    }
+
+    /// Converts both ends of a span into the target unit.
+    pub(crate) fn convert_span(&mut self, span: Span) -> (usize, usize) {
+        (self.convert(span.start), self.convert(span.end))
+    }
+
+    /// Converts an offset in the target unit back into a byte offset, or returns `None` if it
+    /// lies beyond the end of the passage or inside a character.
+    pub(crate) fn byte_offset(&mut self, offset: usize) -> Option<usize> {
+        if self.unit == OffsetUnit::Utf8 {
+            return self.text.is_char_boundary(offset).then_some(offset);
+        }
+
+        if offset < self.last_converted {
+            self.last_byte = 0;
+            self.last_converted = 0;
+        }
+
+        let mut chars = self.text[self.last_byte..].chars();
+        while self.last_converted < offset {
+            let c = chars.next()?;
+            self.last_byte += c.len_utf8();
+            self.last_converted += match self.unit {
+                OffsetUnit::Chars => 1,
+                OffsetUnit::Utf16 => c.len_utf16(),
+                OffsetUnit::Utf8 => unreachable!(),
+            };
+        }
+
+        (self.last_converted == offset).then_some(self.last_byte)
+    }
+
+    /// Converts a span given in the target unit back into bytes, or returns `None` if either end
+    /// is invalid or the span is reversed.
+    pub(crate) fn byte_span(&mut self, start: usize, end: usize) -> Option<Span> {
+        let start = self.byte_offset(start)?;
+        let end = self.byte_offset(end)?;
+
+        (start <= end).then(|| Span::new(start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_touches() {
+        let span = Span::new(2, 4);
+        assert!(span.touches(Span::new(0, 2)));
+        assert!(span.touches(Span::new(3, 3)));
+        assert!(span.touches(Span::new(4, 6)));
+        assert!(!span.touches(Span::new(5, 6)));
+        assert!(!span.touches(Span::new(0, 1)));
+    }
+
+    #[test]
+    fn test_convert_ascii() {
+        for unit in [OffsetUnit::Chars, OffsetUnit::Utf8, OffsetUnit::Utf16] {
+            let mut converter = OffsetConverter::new("hello", unit);
+            assert_eq!(converter.convert_span(Span::new(1, 4)), (1, 4));
+        }
+    }
+
+    #[test]
+    fn test_convert_non_ascii() {
+        // 'é' is one char, two UTF-8 bytes and one UTF-16 unit;
+        // '😀' is one char, four UTF-8 bytes and two UTF-16 units.
+        let text = "é😀ab";
+        let span = Span::new(6, 8);
+
+        let mut chars = OffsetConverter::new(text, OffsetUnit::Chars);
+        assert_eq!(chars.convert_span(span), (2, 4));
+
+        let mut bytes = OffsetConverter::new(text, OffsetUnit::Utf8);
+        assert_eq!(bytes.convert_span(span), (6, 8));
+
+        let mut utf16 = OffsetConverter::new(text, OffsetUnit::Utf16);
+        assert_eq!(utf16.convert_span(span), (3, 5));
+    }
+
+    #[test]
+    fn test_byte_offset_round_trips() {
+        let text = "é😀ab";
+
+        for unit in [OffsetUnit::Chars, OffsetUnit::Utf8, OffsetUnit::Utf16] {
+            for byte in [0, 2, 6, 7, 8] {
+                let offset = OffsetConverter::new(text, unit).convert(byte);
+                let mut converter = OffsetConverter::new(text, unit);
+                assert_eq!(converter.byte_offset(offset), Some(byte), "{unit:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_byte_offset_rejects_invalid_offsets() {
+        let text = "é😀ab";
+
+        // Inside the 'é', inside the surrogate pair, and past the end respectively.
+        assert_eq!(
+            OffsetConverter::new(text, OffsetUnit::Utf8).byte_offset(1),
+            None
+        );
+        assert_eq!(
+            OffsetConverter::new(text, OffsetUnit::Utf16).byte_offset(2),
+            None
+        );
+        assert_eq!(
+            OffsetConverter::new(text, OffsetUnit::Chars).byte_offset(5),
+            None
+        );
+
+        let mut converter = OffsetConverter::new(text, OffsetUnit::Chars);
+        assert_eq!(converter.byte_span(3, 2), None);
+        assert_eq!(converter.byte_span(2, 3), Some(Span::new(6, 7)));
+    }
+
+    #[test]
+    fn test_convert_out_of_order() {
+        let text = "é😀ab";
+        let mut converter = OffsetConverter::new(text, OffsetUnit::Utf16);
+        assert_eq!(converter.convert(7), 4);
+        assert_eq!(converter.convert(2), 1);
+        assert_eq!(converter.convert(8), 5);
+    }
+}
@@ -0,0 +1,27 @@
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::case::match_case;
+
+/// Finds the set in `sets` which contains `word`, ignoring case.
+pub(crate) fn find_matching_set<'a>(
+    sets: &'a [Vec<String>],
+    word: &str,
+) -> Option<&'a Vec<String>> {
+    let word = word.to_lowercase();
+    sets.iter().find(|set| set.contains(&word))
+}
+
+/// Picks another word from the set in `sets` which contains `word`, capitalized like `word`.
+pub(crate) fn get_alternative<R: Rng>(
+    sets: &[Vec<String>],
+    word: &str,
+    rng: &mut R,
+) -> Option<String> {
+    let set = find_matching_set(sets, word)?;
+    let alternatives: Vec<&String> = set
+        .iter()
+        .filter(|w| w.to_lowercase() != word.to_lowercase())
+        .collect();
+
+    alternatives.choose(rng).map(|alt| match_case(word, alt))
+}